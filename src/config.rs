@@ -1,4 +1,4 @@
-use std::{fs::File, io, iter, path::Path};
+use std::{collections::HashMap, fs::File, io, iter, path::Path};
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
@@ -8,7 +8,15 @@ use crow::{Context, LoadTextureError};
 
 use crow_anim::{Animation, AnimationHandle, AnimationStorage};
 
-use crate::{data::PlayerAnimations, input::Key, spritesheet::SpriteSheet};
+use crate::{
+    data::{
+        AnimationCondition, AnimationController, AnimationTransition, ColliderType,
+        EnemyAttackPhase, Interaction, ProjectileHit,
+    },
+    input::{InputAction, Key},
+    spritesheet::SpriteSheet,
+    time::Time,
+};
 
 #[derive(Debug)]
 pub enum LoadError {
@@ -92,8 +100,49 @@ pub struct GameConfig {
     pub input: InputConfig,
     pub camera: CameraConfig,
     pub gravity: GravityConfig,
+    pub collision: CollisionConfig,
     pub input_buffer: InputBufferConfig,
     pub player: PlayerConfig,
+    pub goddess: GoddessConfig,
+    /// The attacks of all enemies, referenced by name in `EnemyData::attack`
+    pub enemy_attacks: HashMap<String, EnemyAttackConfig>,
+    /// All bosses, referenced by name in `BossData::boss`
+    pub bosses: HashMap<String, BossConfig>,
+    /// All projectiles, referenced by name in `ProjectileSpawnConfig::projectile`
+    pub projectiles: HashMap<String, ProjectileConfig>,
+}
+
+impl GameConfig {
+    /// Checks the references between configs and values the game can not handle,
+    /// returning a description of the first invalid value.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, attack) in self.enemy_attacks.iter() {
+            if attack.active.is_empty() {
+                return Err(format!("Enemy attack {} has no active phases", name));
+            }
+
+            if attack.windup == 0 {
+                warn!(
+                    "Enemy attack {} has no windup and can not be anticipated",
+                    name
+                );
+            }
+        }
+
+        for (name, boss) in self.bosses.iter() {
+            if boss.phases.is_empty() {
+                return Err(format!("Boss {} has no phases", name));
+            }
+
+            for attack in boss.phases.iter().flat_map(|phase| phase.pattern.iter()) {
+                if !self.enemy_attacks.contains_key(attack) {
+                    return Err(format!("Boss {} uses unknown attack {}", name, attack));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -111,12 +160,25 @@ pub struct InputConfig {
     pub right: Key,
     pub left: Key,
     pub jump: Key,
+    pub roll: Key,
+    pub attack: Key,
     /// TODO: remove me before releasing this game
     pub debug_toggle: Key,
     /// TODO: remove me
     pub editor_tile: Key,
 }
 
+impl InputConfig {
+    pub fn action_key(&self, action: InputAction) -> Key {
+        match action {
+            InputAction::Jump => self.jump,
+            InputAction::Roll => self.roll,
+            InputAction::Attack => self.attack,
+            InputAction::Drop => self.down,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CameraConfig {
     pub offset: (f32, f32),
@@ -128,6 +190,18 @@ pub struct PlayerConfig {
     pub movement_speed: f32,
     pub grounded_acceleration: f32,
     pub airborne_acceleration: f32,
+    /// The frames after walking off a ledge during which the player can still jump
+    pub coyote_frames: usize,
+    /// The maximum upwards speed after releasing the jump key during a jump
+    pub jump_cut_speed: f32,
+    pub wall: WallConfig,
+    /// The speed while climbing up or down a ladder
+    pub climb_speed: f32,
+    pub ledge: LedgeConfig,
+    /// The frames during which the player ignores a bridge after dropping through it
+    pub drop_through_frames: usize,
+    pub roll: RollConfig,
+    pub combo: Vec<AttackConfig>,
 }
 
 impl Default for PlayerConfig {
@@ -137,6 +211,272 @@ impl Default for PlayerConfig {
             movement_speed: 100.0,
             grounded_acceleration: 850.0,
             airborne_acceleration: 250.0,
+            coyote_frames: 5,
+            jump_cut_speed: 100.0,
+            wall: WallConfig::default(),
+            climb_speed: 60.0,
+            ledge: LedgeConfig::default(),
+            drop_through_frames: 10,
+            roll: RollConfig::default(),
+            combo: vec![
+                AttackConfig {
+                    startup: 4,
+                    active: 3,
+                    recovery: 10,
+                    hitbox_offset: (5.0, 2.0),
+                    hitbox_size: (14.0, 10.0),
+                    damage: 1.0,
+                },
+                AttackConfig {
+                    startup: 4,
+                    active: 3,
+                    recovery: 10,
+                    hitbox_offset: (5.0, 4.0),
+                    hitbox_size: (14.0, 10.0),
+                    damage: 1.0,
+                },
+                AttackConfig {
+                    startup: 6,
+                    active: 4,
+                    recovery: 16,
+                    hitbox_offset: (4.0, 0.0),
+                    hitbox_size: (18.0, 14.0),
+                    damage: 2.0,
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallConfig {
+    /// The maximum falling speed while sliding down a wall
+    pub slide_speed: f32,
+    /// The horizontal and vertical speed of a wall jump, the horizontal
+    /// speed always points away from the wall
+    pub jump_speed: (f32, f32),
+    /// The frames after a wall jump during which horizontal input is ignored
+    pub jump_input_lock: usize,
+}
+
+impl Default for WallConfig {
+    fn default() -> Self {
+        WallConfig {
+            slide_speed: 40.0,
+            jump_speed: (120.0, 260.0),
+            jump_input_lock: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgeConfig {
+    /// The distance between the top of the player and the top of a held ledge
+    pub hang_offset: f32,
+    /// The maximum horizontal distance between the player and a grabbed ledge
+    pub grab_distance: f32,
+    /// The duration of climbing up a ledge in frames
+    pub climb_frames: usize,
+}
+
+impl Default for LedgeConfig {
+    fn default() -> Self {
+        LedgeConfig {
+            hang_offset: 3.0,
+            grab_distance: 2.0,
+            climb_frames: 12,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollConfig {
+    /// The distance covered by an uninterrupted roll
+    pub distance: f32,
+    /// The duration of a roll in frames
+    pub duration: usize,
+    /// The first and last frame of a roll during which the player is invincible
+    pub invincibility: (usize, usize),
+    /// The frames after a roll during which the player can not roll again
+    pub cooldown: usize,
+}
+
+impl Default for RollConfig {
+    fn default() -> Self {
+        RollConfig {
+            distance: 60.0,
+            duration: 18,
+            invincibility: (2, 14),
+            cooldown: 20,
+        }
+    }
+}
+
+/// A single hit of a melee combo, all durations are in frames.
+///
+/// The next hit of the combo can be started during `recovery`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackConfig {
+    pub startup: usize,
+    pub active: usize,
+    pub recovery: usize,
+    /// The offset of the hitbox relative to the lower right corner of the attacker
+    /// while facing right
+    pub hitbox_offset: (f32, f32),
+    pub hitbox_size: (f32, f32),
+    /// The damage before applying `Stat::AttackDamage` modifiers
+    pub damage: f32,
+}
+
+/// A telegraphed enemy attack, all durations are in frames.
+///
+/// Hitboxes only exist during the `active` phases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyAttackConfig {
+    /// The maximum horizontal distance to the target at which the attack is started
+    pub range: f32,
+    pub windup: usize,
+    /// Multiplies the color of the attacker during the windup, alternating every `flash_interval` frames
+    pub flash: (f32, f32, f32),
+    pub flash_interval: usize,
+    /// Consecutive phases with different hitboxes, e.g. for wide swings
+    pub active: Vec<AttackPhaseConfig>,
+    pub recovery: usize,
+    /// The frames after the recovery until the next attack can be started
+    pub cooldown: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackPhaseConfig {
+    pub frames: usize,
+    pub hitboxes: Vec<HitboxConfig>,
+    /// Spawned once the phase starts
+    #[serde(default)]
+    pub projectiles: Vec<ProjectileSpawnConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileSpawnConfig {
+    /// The name of the projectile in `GameConfig::projectiles`
+    pub projectile: String,
+    /// The offset of the projectile relative to the lower right corner of the attacker
+    /// while facing right
+    pub offset: (f32, f32),
+    /// The initial velocity while facing right
+    pub velocity: (f32, f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileConfig {
+    pub size: (f32, f32),
+    /// The frames until the projectile is removed
+    pub lifetime: usize,
+    /// The gravity scale of the projectile, `None` if it flies straight
+    pub gravity: Option<f32>,
+    pub on_hit: ProjectileHit,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HitboxConfig {
+    /// The offset of the hitbox relative to the lower right corner of the attacker
+    /// while facing right
+    pub offset: (f32, f32),
+    pub size: (f32, f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossConfig {
+    pub health: f32,
+    pub phases: Vec<BossPhaseConfig>,
+    /// The frames between two phases during which the boss does not attack
+    pub phase_transition: usize,
+    /// The duration of the final transformation, which kills the player
+    /// if they did not absorb enough goddess power
+    pub transformation: usize,
+    /// Multiplies the color of the boss during the transformation
+    pub transformation_flash: (f32, f32, f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossPhaseConfig {
+    /// The phase starts once the health of the boss is at most this fraction of its maximum
+    pub health: f32,
+    /// The names of the attacks in `GameConfig::enemy_attacks`, used in order
+    pub pattern: Vec<String>,
+}
+
+impl EnemyAttackConfig {
+    pub fn duration(&self) -> usize {
+        self.windup + self.active.iter().map(|phase| phase.frames).sum::<usize>() + self.recovery
+    }
+
+    pub fn phase(&self, frame: usize) -> EnemyAttackPhase {
+        if frame < self.windup {
+            EnemyAttackPhase::Windup
+        } else if self.active_phase(frame).is_some() {
+            EnemyAttackPhase::Active
+        } else {
+            EnemyAttackPhase::Recovery
+        }
+    }
+
+    /// Returns the index of the active phase at `frame`.
+    pub fn active_phase(&self, frame: usize) -> Option<usize> {
+        let mut start = self.windup;
+        for (i, phase) in self.active.iter().enumerate() {
+            if start <= frame && frame < start + phase.frames {
+                return Some(i);
+            }
+            start += phase.frames;
+        }
+
+        None
+    }
+
+    /// Returns `true` if the attacker is highlighted at `frame`.
+    pub fn is_flashing(&self, frame: usize) -> bool {
+        frame < self.windup && (frame / self.flash_interval.max(1)) % 2 == 0
+    }
+}
+
+impl AttackConfig {
+    pub fn duration(&self) -> usize {
+        self.startup + self.active + self.recovery
+    }
+
+    pub fn is_active(&self, frame: usize) -> bool {
+        self.startup <= frame && frame < self.startup + self.active
+    }
+
+    pub fn is_recovering(&self, frame: usize) -> bool {
+        self.startup + self.active <= frame
+    }
+}
+
+impl RollConfig {
+    pub fn speed(&self, time: &Time) -> f32 {
+        self.distance / (self.duration as f32 * time.fixed_seconds())
+    }
+
+    pub fn is_invincible(&self, frame: usize) -> bool {
+        self.invincibility.0 <= frame && frame <= self.invincibility.1
+    }
+}
+
+/// The effects of goddess power, which is absorbed by destroying shrines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoddessConfig {
+    /// The increase of attack damage for each destroyed shrine, `0.1` adds 10%
+    pub damage_bonus: f32,
+    /// The power required to survive the last transformation of the sorcerer
+    pub required_power: usize,
+}
+
+impl Default for GoddessConfig {
+    fn default() -> Self {
+        GoddessConfig {
+            damage_bonus: 0.1,
+            required_power: 5,
         }
     }
 }
@@ -156,15 +496,186 @@ impl Default for GravityConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionConfig {
+    pub source: ColliderType,
+    pub target: ColliderType,
+    pub interaction: Interaction,
+}
+
+/// The layer interaction matrix, colliders of layers without
+/// an interaction ignore each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollisionConfig {
+    pub interactions: Vec<InteractionConfig>,
+}
+
+impl CollisionConfig {
+    /// Returns how colliders of layer `source` act upon colliders of layer `target`.
+    pub fn interaction(&self, source: ColliderType, target: ColliderType) -> Option<Interaction> {
+        self.interactions
+            .iter()
+            .find(|i| i.source == source && i.target == target)
+            .map(|i| i.interaction)
+    }
+
+    /// Returns all layers which are `Solid` for colliders of layer `target`.
+    pub fn solid_layers(&self, target: ColliderType) -> Vec<ColliderType> {
+        self.interactions
+            .iter()
+            .filter(|i| i.target == target && i.interaction == Interaction::Solid)
+            .map(|i| i.source)
+            .collect()
+    }
+
+    /// Returns all layers which are `OneWay` for colliders of layer `target`.
+    pub fn one_way_layers(&self, target: ColliderType) -> Vec<ColliderType> {
+        self.interactions
+            .iter()
+            .filter(|i| i.target == target && i.interaction == Interaction::OneWay)
+            .map(|i| i.source)
+            .collect()
+    }
+
+    /// Returns all layers damaged by colliders of layer `source`.
+    pub fn damage_targets(&self, source: ColliderType) -> Vec<ColliderType> {
+        self.interactions
+            .iter()
+            .filter(|i| i.source == source && i.interaction == Interaction::Damage)
+            .map(|i| i.target)
+            .collect()
+    }
+
+    /// Returns all `(source, target)` layer pairs with a `Sensor` interaction.
+    pub fn sensor_pairs(&self) -> Vec<(ColliderType, ColliderType)> {
+        self.interactions
+            .iter()
+            .filter(|i| i.interaction == Interaction::Sensor)
+            .map(|i| (i.source, i.target))
+            .collect()
+    }
+
+    /// Returns all layers which prevent colliders of layer `target` from falling through them.
+    pub fn ground_layers(&self, target: ColliderType) -> Vec<ColliderType> {
+        self.interactions
+            .iter()
+            .filter(|i| i.target == target)
+            .filter(|i| match i.interaction {
+                Interaction::Solid | Interaction::OneWay => true,
+                Interaction::Damage | Interaction::Sensor | Interaction::Impact => false,
+            })
+            .map(|i| i.source)
+            .collect()
+    }
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        let interaction = |source, target, interaction| InteractionConfig {
+            source,
+            target,
+            interaction,
+        };
+
+        CollisionConfig {
+            interactions: vec![
+                interaction(
+                    ColliderType::Environment,
+                    ColliderType::Player,
+                    Interaction::Solid,
+                ),
+                interaction(
+                    ColliderType::Bridge,
+                    ColliderType::Player,
+                    Interaction::OneWay,
+                ),
+                interaction(
+                    ColliderType::PlayerDamage,
+                    ColliderType::Player,
+                    Interaction::Damage,
+                ),
+                interaction(
+                    ColliderType::CameraRestriction,
+                    ColliderType::Camera,
+                    Interaction::Solid,
+                ),
+                interaction(
+                    ColliderType::Trigger,
+                    ColliderType::Player,
+                    Interaction::Sensor,
+                ),
+                interaction(
+                    ColliderType::Shrine,
+                    ColliderType::Player,
+                    Interaction::Sensor,
+                ),
+                interaction(
+                    ColliderType::PlayerAttack,
+                    ColliderType::Shrine,
+                    Interaction::Damage,
+                ),
+                interaction(
+                    ColliderType::Environment,
+                    ColliderType::Enemy,
+                    Interaction::Solid,
+                ),
+                interaction(
+                    ColliderType::Bridge,
+                    ColliderType::Enemy,
+                    Interaction::OneWay,
+                ),
+                interaction(
+                    ColliderType::Enemy,
+                    ColliderType::Player,
+                    Interaction::Damage,
+                ),
+                interaction(
+                    ColliderType::PlayerAttack,
+                    ColliderType::Enemy,
+                    Interaction::Damage,
+                ),
+                interaction(
+                    ColliderType::Projectile,
+                    ColliderType::Player,
+                    Interaction::Damage,
+                ),
+                interaction(
+                    ColliderType::Environment,
+                    ColliderType::Projectile,
+                    Interaction::Impact,
+                ),
+            ],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InputBufferConfig {
     pub jump_buffer_frames: u8,
+    pub roll_buffer_frames: u8,
+    pub attack_buffer_frames: u8,
+    pub drop_buffer_frames: u8,
+}
+
+impl InputBufferConfig {
+    /// The frames during which a press of `action` stays buffered.
+    pub fn frames(&self, action: InputAction) -> u8 {
+        match action {
+            InputAction::Jump => self.jump_buffer_frames,
+            InputAction::Roll => self.roll_buffer_frames,
+            InputAction::Attack => self.attack_buffer_frames,
+            InputAction::Drop => self.drop_buffer_frames,
+        }
+    }
 }
 
 impl Default for InputBufferConfig {
     fn default() -> Self {
         InputBufferConfig {
             jump_buffer_frames: 3,
+            roll_buffer_frames: 6,
+            attack_buffer_frames: 8,
+            drop_buffer_frames: 4,
         }
     }
 }
@@ -191,14 +702,26 @@ pub struct FrameConfig {
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct PlayerAnimationsConfig {
+pub struct AnimationClipConfig {
+    pub frames: Vec<FrameConfig>,
+    /// The clip started once this clip is finished, repeats itself if `None`
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationTransitionConfig {
+    pub from: Vec<String>,
+    pub to: String,
+    pub conditions: Vec<AnimationCondition>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnimationControllerConfig {
     pub spritesheets: Vec<String>,
-    pub idle: Vec<FrameConfig>,
-    pub running: Vec<FrameConfig>,
-    pub run_into_obstacle: Vec<FrameConfig>,
-    pub jumping: Vec<FrameConfig>,
-    pub start_falling: Vec<FrameConfig>,
-    pub falling: Vec<FrameConfig>,
+    pub initial: String,
+    pub clips: HashMap<String, AnimationClipConfig>,
+    pub transitions: Vec<AnimationTransitionConfig>,
 }
 
 fn add_animation(
@@ -228,11 +751,12 @@ fn add_animation(
     handle
 }
 
-impl PlayerAnimations {
+impl AnimationController {
+    /// Loads all clips of `config` into `storage`.
     pub fn from_config(
         ctx: &mut Context,
         storage: &mut AnimationStorage,
-        config: PlayerAnimationsConfig,
+        config: AnimationControllerConfig,
     ) -> Result<Self, LoadTextureError> {
         let sheets = config
             .spritesheets
@@ -240,21 +764,106 @@ impl PlayerAnimations {
             .map(|path| SpriteSheet::from_config(ctx, &SpriteSheetConfig::load(path).unwrap()))
             .collect::<Result<Vec<_>, _>>()?;
 
-        let idle = add_animation(storage, &sheets, config.idle, None);
-        let running = add_animation(storage, &sheets, config.running, None);
-        let run_into_obstacle =
-            add_animation(storage, &sheets, config.run_into_obstacle, Some(idle));
-        let falling = add_animation(storage, &sheets, config.falling, None);
-        let start_falling = add_animation(storage, &sheets, config.start_falling, Some(falling));
-        let jumping = add_animation(storage, &sheets, config.jumping, Some(start_falling));
-
-        Ok(PlayerAnimations {
-            idle,
-            running,
-            run_into_obstacle,
-            jumping,
-            start_falling,
-            falling,
+        let mut nexts = Vec::new();
+        let mut clips = HashMap::new();
+        for (name, clip) in config.clips {
+            let handle = add_animation(storage, &sheets, clip.frames, None);
+            if let Some(next) = clip.next {
+                nexts.push((handle, next));
+            }
+            clips.insert(name, handle);
+        }
+
+        let clip = |name: &str| -> AnimationHandle {
+            *clips
+                .get(name)
+                .unwrap_or_else(|| panic!("unknown animation clip: {}", name))
+        };
+
+        for (handle, next) in nexts {
+            storage.get_mut(handle).next = clip(&next);
+        }
+
+        let transitions = config
+            .transitions
+            .into_iter()
+            .map(|transition| AnimationTransition {
+                from: transition.from.iter().map(|name| clip(name)).collect(),
+                to: clip(&transition.to),
+                conditions: transition.conditions,
+            })
+            .collect();
+
+        Ok(AnimationController {
+            initial: clip(&config.initial),
+            transitions,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attack() -> EnemyAttackConfig {
+        let phase = |frames| AttackPhaseConfig {
+            frames,
+            hitboxes: Vec::new(),
+            projectiles: Vec::new(),
+        };
+
+        EnemyAttackConfig {
+            range: 30.0,
+            windup: 10,
+            flash: (1.0, 1.0, 1.0),
+            flash_interval: 4,
+            active: vec![phase(3), phase(5)],
+            recovery: 6,
+            cooldown: 20,
+        }
+    }
+
+    #[test]
+    fn enemy_attack_duration() {
+        assert_eq!(attack().duration(), 24);
+    }
+
+    #[test]
+    fn enemy_attack_phase() {
+        let attack = attack();
+        assert_eq!(attack.phase(0), EnemyAttackPhase::Windup);
+        assert_eq!(attack.phase(9), EnemyAttackPhase::Windup);
+        assert_eq!(attack.phase(10), EnemyAttackPhase::Active);
+        assert_eq!(attack.phase(17), EnemyAttackPhase::Active);
+        assert_eq!(attack.phase(18), EnemyAttackPhase::Recovery);
+        assert_eq!(attack.phase(23), EnemyAttackPhase::Recovery);
+    }
+
+    #[test]
+    fn enemy_attack_active_phase() {
+        let attack = attack();
+        assert_eq!(attack.active_phase(9), None);
+        assert_eq!(attack.active_phase(10), Some(0));
+        assert_eq!(attack.active_phase(12), Some(0));
+        assert_eq!(attack.active_phase(13), Some(1));
+        assert_eq!(attack.active_phase(17), Some(1));
+        assert_eq!(attack.active_phase(18), None);
+    }
+
+    #[test]
+    fn enemy_attack_is_flashing() {
+        let attack = attack();
+        assert!(attack.is_flashing(0));
+        assert!(attack.is_flashing(3));
+        assert!(!attack.is_flashing(4));
+        assert!(attack.is_flashing(8));
+        assert!(!attack.is_flashing(10));
+
+        let attack = EnemyAttackConfig {
+            flash_interval: 0,
+            ..attack
+        };
+        assert!(attack.is_flashing(0));
+        assert!(!attack.is_flashing(1));
+    }
+}
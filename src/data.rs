@@ -22,11 +22,39 @@ pub struct Components {
     pub grounded: Storage<Grounded>,
     pub wall_collisions: Storage<WallCollision>,
     pub gravity: Storage<Gravity>,
+    pub gravity_zones: SparseStorage<GravityZone>,
+    pub shrines: SparseStorage<Shrine>,
+    pub healths: SparseStorage<Health>,
+    pub hitboxes: SparseStorage<Hitbox>,
+    pub stat_modifiers: SparseStorage<StatModifiers>,
+    pub factions: SparseStorage<Faction>,
+    pub enemies: SparseStorage<Enemy>,
+    pub behaviours: SparseStorage<Behaviour>,
+    pub behaviour_states: SparseStorage<BehaviourState>,
+    pub attackers: SparseStorage<Attacker>,
+    pub flashes: SparseStorage<Flash>,
+    pub bosses: SparseStorage<Boss>,
+    pub projectiles: SparseStorage<Projectile>,
+    pub crumbling_platforms: SparseStorage<CrumblingPlatform>,
+    pub falling_blocks: SparseStorage<FallingBlock>,
+    pub saws: SparseStorage<Saw>,
+    pub slopes: SparseStorage<Slope>,
+    pub surfaces: SparseStorage<Surface>,
     pub ignore_bridges: SparseStorage<IgnoreBridges>,
+    pub drop_throughs: SparseStorage<DropThrough>,
+    pub ignore_gravity: SparseStorage<IgnoreGravity>,
     pub player_state: SparseStorage<PlayerState>,
-    pub player_animations: SparseStorage<PlayerAnimations>,
+    pub animation_controllers: SparseStorage<AnimationController>,
+    pub rolls: SparseStorage<Roll>,
+    pub attacks: SparseStorage<Attack>,
+    pub ledge_grabs: SparseStorage<LedgeGrab>,
+    pub roll_cooldowns: SparseStorage<RollCooldown>,
+    pub coyote_time: SparseStorage<CoyoteTime>,
+    pub input_locks: SparseStorage<InputLock>,
+    pub jumping: SparseStorage<Jumping>,
     pub depths: Storage<Depth>,
     pub mirrored: SparseStorage<Mirrored>,
+    pub resettables: SparseStorage<Resettable>,
     pub cameras: SparseStorage<Camera>,
 }
 
@@ -47,6 +75,11 @@ impl Components {
 
     pub fn delete_entity(&mut self, e: Entity) {
         self.deleted.push(e);
+        self.remove_components(e);
+    }
+
+    /// Removes all components of `e` without deleting the entity itself.
+    pub fn remove_components(&mut self, e: Entity) {
         self.positions.remove(e);
         self.sprites.remove(e);
         self.animations.remove(e);
@@ -56,11 +89,39 @@ impl Components {
         self.grounded.remove(e);
         self.wall_collisions.remove(e);
         self.gravity.remove(e);
+        self.gravity_zones.remove(e);
+        self.shrines.remove(e);
+        self.healths.remove(e);
+        self.hitboxes.remove(e);
+        self.stat_modifiers.remove(e);
+        self.factions.remove(e);
+        self.enemies.remove(e);
+        self.behaviours.remove(e);
+        self.behaviour_states.remove(e);
+        self.attackers.remove(e);
+        self.flashes.remove(e);
+        self.bosses.remove(e);
+        self.projectiles.remove(e);
+        self.crumbling_platforms.remove(e);
+        self.falling_blocks.remove(e);
+        self.saws.remove(e);
+        self.slopes.remove(e);
+        self.surfaces.remove(e);
         self.ignore_bridges.remove(e);
+        self.drop_throughs.remove(e);
+        self.ignore_gravity.remove(e);
         self.player_state.remove(e);
-        self.player_animations.remove(e);
+        self.animation_controllers.remove(e);
+        self.rolls.remove(e);
+        self.attacks.remove(e);
+        self.ledge_grabs.remove(e);
+        self.roll_cooldowns.remove(e);
+        self.coyote_time.remove(e);
+        self.input_locks.remove(e);
+        self.jumping.remove(e);
         self.depths.remove(e);
         self.mirrored.remove(e);
+        self.resettables.remove(e);
         self.cameras.remove(e);
     }
 }
@@ -130,25 +191,104 @@ impl From<Depth> for f32 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum PlayerState {
     Grounded,
     Airborne,
+    Rolling,
+    Attacking,
+    WallSliding,
+    Climbing,
+    LedgeHang,
+    LedgeClimb,
     Dying,
     Dead,
 }
 
-pub struct PlayerAnimations {
-    pub idle: AnimationHandle,
-    pub running: AnimationHandle,
-    pub run_into_obstacle: AnimationHandle,
-    /// run once after jumping -> falling,
-    pub jumping: AnimationHandle,
-    /// run once during a jump/fall -> falling,
-    pub start_falling: AnimationHandle,
-    pub falling: AnimationHandle,
+/// Selects the current animation of an entity, see `AnimationControllerSystem`.
+///
+/// Transitions after an animation finished are stored in `Animation::next` instead.
+#[derive(Debug, Clone)]
+pub struct AnimationController {
+    pub initial: AnimationHandle,
+    pub transitions: Vec<AnimationTransition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationTransition {
+    /// The animations this transition can start from, all animations if empty
+    pub from: Vec<AnimationHandle>,
+    pub to: AnimationHandle,
+    /// All conditions have to hold for this transition to be taken
+    pub conditions: Vec<AnimationCondition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum AnimationCondition {
+    Grounded(bool),
+    WallContact(bool),
+    /// The absolute horizontal speed is at least the given value
+    SpeedXAbove(f32),
+    /// The absolute horizontal speed is less than the given value
+    SpeedXBelow(f32),
+    /// The vertical velocity is greater than the given value
+    VelocityYAbove(f32),
+    /// The vertical velocity is less than or equal to the given value
+    VelocityYBelow(f32),
+    PlayerState(PlayerState),
+    /// The index of the current hit of a melee combo
+    AttackHit(usize),
+    /// The current phase of an enemy attack
+    EnemyAttackPhase(EnemyAttackPhase),
+}
+
+/// The progress of a dodge roll, only exists while in `PlayerState::Rolling`.
+#[derive(Debug, Clone, Copy)]
+pub struct Roll {
+    pub frame: usize,
+    /// `-1.0` when rolling to the left and `1.0` when rolling to the right
+    pub direction: f32,
+}
+
+/// The progress of a melee combo, only exists while in `PlayerState::Attacking`
+/// or until the hitbox has been removed.
+#[derive(Debug, Clone, Copy)]
+pub struct Attack {
+    /// the index of the current hit in `PlayerConfig::combo`
+    pub hit: usize,
+    pub frame: usize,
+    pub hitbox: Option<Entity>,
+}
+
+/// The ledge held by the player, only exists while in `PlayerState::LedgeHang`
+/// or `PlayerState::LedgeClimb`.
+#[derive(Debug, Clone, Copy)]
+pub struct LedgeGrab {
+    /// the upper corner of the ledge
+    pub corner: Position,
+    /// `-1.0` if the ledge is to the left of the player and `1.0` otherwise
+    pub direction: f32,
+    /// the frames spent climbing up
+    pub frame: usize,
 }
 
+/// The remaining frames until the entity can roll again.
+#[derive(Debug, Clone, Copy)]
+pub struct RollCooldown(pub usize);
+
+/// The remaining frames during which an entity which walked off a ledge can still jump.
+#[derive(Debug, Clone, Copy)]
+pub struct CoyoteTime(pub usize);
+
+/// The remaining frames during which horizontal input of the entity is ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct InputLock(pub usize);
+
+/// The entity is rising after a jump which can still be cut short
+/// by releasing the jump key.
+#[derive(Debug, Clone, Copy)]
+pub struct Jumping;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Collider {
     pub w: f32,
@@ -178,31 +318,174 @@ impl Collider {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The collision layer of a collider.
+///
+/// How colliders of different layers interact is defined using `CollisionConfig::interactions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum ColliderType {
     Environment,
     Player,
     Bridge,
     PlayerDamage,
+    PlayerAttack,
     Camera,
     CameraRestriction,
+    /// non solid areas reporting when entities enter or leave them
+    Trigger,
+    /// climbable areas, found using `physics::overlapping`
+    Ladder,
+    /// areas changing the gravity of entities inside of them, found through their chunk
+    GravityZone,
+    /// updates the save once the player walks past it, can be destroyed by attacks
+    Shrine,
+    /// the body of an enemy, damaged by `PlayerAttack` and damaging the player on contact
+    Enemy,
+    /// damages the player, reacts to hitting `Environment` colliders
+    Projectile,
 }
 
+/// How a collider affects colliders of a different layer it overlaps with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Interaction {
+    /// prevents the other collider from moving into it
+    Solid,
+    /// only solid when entered from above
+    OneWay,
+    /// damages the other entity
+    Damage,
+    /// only records the overlap
+    Sensor,
+    /// stops the other collider, which reacts to the hit itself, e.g. projectiles
+    Impact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SlopeAngle {
+    /// 45°
+    Steep,
+    /// the lower half of a 22.5° slope spanning two tiles
+    ShallowLower,
+    /// the upper half of a 22.5° slope spanning two tiles
+    ShallowUpper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SlopeDirection {
+    Left,
+    Right,
+}
+
+/// Changes the shape of a solid collider into a triangle,
+/// the solid part is below the surface unless `ceiling` is true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Slope {
+    pub angle: SlopeAngle,
+    /// the direction in which the surface gets further away from the base of the slope
+    pub rising: SlopeDirection,
+    pub ceiling: bool,
+}
+
+impl Slope {
+    /// Every possible slope, ordered by `ceiling`, `rising` and `angle`.
+    pub fn all() -> impl Iterator<Item = Slope> {
+        static CEILING: [bool; 2] = [false, true];
+        static RISING: [SlopeDirection; 2] = [SlopeDirection::Right, SlopeDirection::Left];
+        static ANGLES: [SlopeAngle; 3] = [
+            SlopeAngle::Steep,
+            SlopeAngle::ShallowLower,
+            SlopeAngle::ShallowUpper,
+        ];
+
+        CEILING.iter().flat_map(|&ceiling| {
+            RISING.iter().flat_map(move |&rising| {
+                ANGLES.iter().map(move |&angle| Slope {
+                    angle,
+                    rising,
+                    ceiling,
+                })
+            })
+        })
+    }
+
+    /// The vertical distance per horizontal unit.
+    pub fn steepness(self) -> f32 {
+        match self.angle {
+            SlopeAngle::Steep => 1.0,
+            SlopeAngle::ShallowLower | SlopeAngle::ShallowUpper => 0.5,
+        }
+    }
+
+    /// The thickness of the solid part at `x`, relative to the left border of `col`.
+    ///
+    /// `x` is clamped to the collider, so positions outside of the slope
+    /// use the height of the nearest border.
+    pub fn height(self, x: f32, col: &Collider) -> f32 {
+        let t = (x / col.w).max(0.0).min(1.0);
+        let t = match self.rising {
+            SlopeDirection::Right => t,
+            SlopeDirection::Left => 1.0 - t,
+        };
+
+        col.h
+            * match self.angle {
+                SlopeAngle::Steep => t,
+                SlopeAngle::ShallowLower => t * 0.5,
+                SlopeAngle::ShallowUpper => 0.5 + t * 0.5,
+            }
+    }
+
+    /// Returns `true` if `x`, relative to the left border of `col`,
+    /// is beyond the lower end of this slope.
+    pub fn is_past_lower_end(self, x: f32, col: &Collider) -> bool {
+        match self.rising {
+            SlopeDirection::Right => x < 0.0,
+            SlopeDirection::Left => x > col.w,
+        }
+    }
+}
+
+/// A collision between two entities, the first entity acts upon the second one.
 #[derive(Debug, Clone, Copy)]
 pub struct Collision(pub Entity, pub Entity);
 
 #[derive(Debug, Default)]
 pub struct Collisions {
     pub fixed: Vec<Collision>,
-    pub bridge: Vec<Collision>,
-    pub player_damage: Vec<Collision>,
+    pub one_way: Vec<Collision>,
+    pub slope: Vec<Collision>,
+    pub damage: Vec<Collision>,
+    pub sensor: Vec<Collision>,
+    pub impact: Vec<Collision>,
 }
 
 impl Collisions {
     pub fn clear(&mut self) {
         self.fixed.clear();
-        self.bridge.clear();
-        self.player_damage.clear();
+        self.one_way.clear();
+        self.slope.clear();
+        self.damage.clear();
+        self.sensor.clear();
+        self.impact.clear();
+    }
+}
+
+/// Changes in the overlap of sensors and other entities,
+/// each `Collision` is `(sensor, other)`.
+#[derive(Debug, Default)]
+pub struct TriggerEvents {
+    /// started overlapping this frame
+    pub enter: Vec<Collision>,
+    /// already overlapped during the previous frame
+    pub stay: Vec<Collision>,
+    /// stopped overlapping this frame
+    pub exit: Vec<Collision>,
+}
+
+impl TriggerEvents {
+    pub fn clear(&mut self) {
+        self.enter.clear();
+        self.stay.clear();
+        self.exit.clear();
     }
 }
 
@@ -249,21 +532,376 @@ impl BitAnd for CollisionDirection {
     }
 }
 
+/// Stores the entity this entity is standing on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grounded(pub Entity);
+
+/// Changes how entities move while standing on this entity.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Surface {
+    /// multiplies the acceleration of entities on this surface,
+    /// `< 1.0` for slippery and `> 1.0` for sticky surfaces
+    pub acceleration: f32,
+    /// the horizontal speed entities on this surface move at while idle
+    pub conveyor_speed: f32,
+}
+
+impl Default for Surface {
+    fn default() -> Self {
+        Surface {
+            acceleration: 1.0,
+            conveyor_speed: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Gravity {
+    /// multiplies `GravityConfig::acceleration`
+    pub scale: f32,
+    /// overrides `GravityConfig::terminal_velocity`
+    pub terminal_velocity: Option<f32>,
+}
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Gravity {
+            scale: 1.0,
+            terminal_velocity: None,
+        }
+    }
+}
+
+/// Changes the gravity of all entities whose center is inside of the collider of this entity,
+/// a negative `scale` inverts gravity.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct GravityZone {
+    pub scale: f32,
+}
+
+/// A checkpoint, the player respawns at the last shrine they walked past.
+#[derive(Debug, Clone, Copy)]
+pub struct Shrine {
+    pub id: ShrineId,
+    /// the position the player respawns at
+    pub spawn: Position,
+}
+
+/// Identifies a shrine across chunk reloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShrineId {
+    pub chunk: (i32, i32),
+    /// the index of the shrine in `ChunkData::shrines`
+    pub index: usize,
+}
+
+/// Entities only attack entities of a different faction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
+
+/// An enemy spawned by a chunk.
+#[derive(Debug, Clone, Copy)]
+pub struct Enemy {
+    pub id: EnemyId,
+    /// the spawn position of the enemy, which it patrols around
+    pub post: Position,
+    /// the initial health, restored once the player respawns
+    pub health: f32,
+}
+
+/// Identifies an enemy across chunk reloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnemyId {
+    pub chunk: (i32, i32),
+    /// the index of the enemy in `ChunkData::enemies`
+    pub index: usize,
+}
+
+/// How an enemy moves, used by the `EnemySystem`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Behaviour {
+    /// the speed while patrolling or returning to its post
+    pub speed: f32,
+    /// the maximum horizontal distance from its post while patrolling, `0.0` to stand still
+    pub patrol_distance: f32,
+    pub chase_speed: f32,
+    /// the distance in which entities of a different faction are noticed
+    pub sight_distance: f32,
+    /// targets further away from the post than this are not chased
+    pub leash_distance: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BehaviourState {
+    /// `direction` is `-1.0` while moving to the left and `1.0` otherwise
+    Patrol {
+        direction: f32,
+    },
+    Chase(Entity),
+    /// walks back to the post after losing its target
+    Return,
+}
+
+impl Default for BehaviourState {
+    fn default() -> Self {
+        BehaviourState::Patrol { direction: 1.0 }
+    }
+}
+
+/// An enemy able to attack.
+#[derive(Debug, Clone)]
+pub struct Attacker {
+    /// the name of the attack in `GameConfig::enemy_attacks`
+    pub attack: String,
+    /// the remaining frames until the next attack can be started
+    pub cooldown: usize,
+    pub current: Option<EnemyAttack>,
+}
+
+/// The progress of an ongoing enemy attack.
+#[derive(Debug, Clone)]
+pub struct EnemyAttack {
+    pub frame: usize,
+    pub phase: EnemyAttackPhase,
+    /// the index of the current phase in `EnemyAttackConfig::active`
+    pub active_phase: Option<usize>,
+    /// the `PlayerDamage` colliders of `active_phase`
+    pub hitboxes: Vec<Entity>,
+}
+
+impl Default for EnemyAttack {
+    fn default() -> Self {
+        EnemyAttack::new()
+    }
+}
+
+impl EnemyAttack {
+    pub fn new() -> Self {
+        EnemyAttack {
+            frame: 0,
+            phase: EnemyAttackPhase::Windup,
+            active_phase: None,
+            hitboxes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum EnemyAttackPhase {
+    Windup,
+    Active,
+    Recovery,
+}
+
+/// A boss fought inside of an arena, controlled by the `BossSystem`.
+#[derive(Debug, Clone)]
+pub struct Boss {
+    pub id: BossId,
+    /// the name of the boss in `GameConfig::bosses`
+    pub name: String,
+    /// the lower left corner of the arena
+    pub arena: Position,
+    pub arena_size: (f32, f32),
+    pub state: BossState,
+    /// the index of the current phase in `BossConfig::phases`
+    pub phase: usize,
+    /// the index of the next attack in the pattern of the current phase
+    pub next_attack: usize,
+    /// entities spawned during the fight, like the walls locking the player inside of the arena
+    pub spawned: Vec<Entity>,
+}
+
+/// Identifies a boss across chunk reloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BossId {
+    pub chunk: (i32, i32),
+    /// the index of the boss in `ChunkData::bosses`
+    pub index: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Grounded;
+pub enum BossState {
+    /// waiting for the player to enter the arena
+    Waiting,
+    Fighting,
+    /// the pause between two phases
+    PhaseTransition {
+        frames: usize,
+    },
+    /// the final transformation after the health of the boss reached `0.0`
+    Transforming {
+        frame: usize,
+    },
+    /// the player did not absorb enough goddess power to survive the transformation
+    Transformed,
+}
 
+/// A pooled entity moving on its own, see `ProjectilePool`.
 #[derive(Debug, Clone, Copy)]
-pub struct Gravity;
+pub struct Projectile {
+    /// the remaining frames until the projectile is removed
+    pub lifetime: usize,
+    pub on_hit: ProjectileHit,
+}
+
+/// What happens once a projectile hits the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ProjectileHit {
+    Destroy,
+    /// reverses the velocity towards the hit collider
+    Bounce,
+    /// stops moving while still damaging the player
+    Stick,
+}
+
+/// A solid platform which breaks shortly after being stood on and is restored later on.
+#[derive(Debug, Clone)]
+pub struct CrumblingPlatform {
+    pub size: (f32, f32),
+    /// the frames between being stood on and breaking
+    pub delay: usize,
+    /// the frames until a broken platform is restored
+    pub respawn: usize,
+    pub state: CrumbleState,
+    /// the sprite of the platform, which is removed while it is broken
+    pub sprite: Sprite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrumbleState {
+    Intact,
+    Crumbling { frames: usize },
+    Broken { frames: usize },
+}
+
+/// A solid block which starts falling once the player passes beneath it.
+#[derive(Debug, Clone, Copy)]
+pub struct FallingBlock {
+    /// the maximum distance between the block and the player below it
+    pub trigger_depth: f32,
+    pub state: FallingState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallingState {
+    Waiting,
+    Falling,
+    Landed,
+}
+
+/// Repeatedly moves along `path`, returning to the first point after reaching the last one.
+#[derive(Debug, Clone)]
+pub struct Saw {
+    pub path: Vec<Position>,
+    pub speed: f32,
+    /// the index of the point the saw is currently moving towards
+    pub target: usize,
+}
+
+/// Multiplies the color of the sprite of this entity.
+#[derive(Debug, Clone, Copy)]
+pub struct Flash(pub (f32, f32, f32));
+
+/// The remaining health of a damageable entity, which is destroyed once it reaches `0.0`.
+#[derive(Debug, Clone, Copy)]
+pub struct Health(pub f32);
+
+/// Damages every entity with `Health` it overlaps with, hitting each entity at most once.
+#[derive(Debug, Clone)]
+pub struct Hitbox {
+    pub damage: f32,
+    /// the entities which were already hit
+    pub hits: Vec<Entity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stat {
+    AttackDamage,
+}
+
+/// Where a `StatModifier` comes from, each source modifies a stat at most once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModifierSource {
+    GoddessPower,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatModifier {
+    pub source: ModifierSource,
+    pub stat: Stat,
+    /// multiplies the base value of `stat`
+    pub factor: f32,
+}
+
+/// All modifiers affecting the stats of an entity.
+#[derive(Debug, Default, Clone)]
+pub struct StatModifiers(pub Vec<StatModifier>);
+
+impl StatModifiers {
+    /// Adds the modifier of `source` for `stat`, replacing the previous one.
+    pub fn set(&mut self, source: ModifierSource, stat: Stat, factor: f32) {
+        self.0.retain(|m| m.source != source || m.stat != stat);
+        self.0.push(StatModifier {
+            source,
+            stat,
+            factor,
+        });
+    }
+
+    /// Applies all modifiers of `stat` to its `base` value.
+    pub fn apply(&self, stat: Stat, base: f32) -> f32 {
+        self.0
+            .iter()
+            .filter(|m| m.stat == stat)
+            .fold(base, |value, m| value * m.factor)
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct IgnoreBridges;
 
+/// Lets an entity fall through specific bridges for a limited time.
+#[derive(Debug, Clone)]
+pub struct DropThrough {
+    pub bridges: Vec<Entity>,
+    /// the remaining frames during which `bridges` are ignored
+    pub frames: usize,
+}
+
+/// Prevents the `GravitySystem` from accelerating this entity.
+#[derive(Debug, Clone, Copy)]
+pub struct IgnoreGravity;
+
+/// The initial state of a world entity, which is restored once the player respawns.
+#[derive(Debug, Clone, Copy)]
+pub struct Resettable {
+    pub position: Position,
+    pub velocity: Velocity,
+}
+
 /// Used during `draw::scene` to horizontally flip sprites based on the collider of the given entity.
 #[derive(Debug, Clone, Copy)]
 pub struct Mirrored;
 
+/// The side of the entity touching a wall.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct WallCollision;
+pub enum WallCollision {
+    Left,
+    Right,
+}
+
+impl WallCollision {
+    /// Returns `-1.0` if the wall is to the left of the entity and `1.0` otherwise.
+    pub fn direction(self) -> f32 {
+        match self {
+            WallCollision::Left => -1.0,
+            WallCollision::Right => 1.0,
+        }
+    }
+}
 
 #[derive(Default, Debug, Clone)]
 pub struct Camera;
@@ -2,27 +2,145 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crow::{Context, LoadTextureError};
+
 #[cfg(feature = "editor")]
 use crow::Texture;
-use crow::{Context, LoadTextureError};
 
 #[cfg(feature = "editor")]
 use crow_anim::Sprite;
 
 use crow_ecs::Entity;
 
-#[cfg(feature = "editor")]
-use crate::data::Depth;
 use crate::{
-    data::{Collider, ColliderType, Components, Position},
+    data::{
+        Attacker, Behaviour, BehaviourState, Boss, BossId, BossState, Collider, ColliderType,
+        Components, CrumbleState, CrumblingPlatform, Depth, Enemy, EnemyId, Faction, FallingBlock,
+        FallingState, Gravity, GravityZone, Health, Position, Resettable, Saw, Shrine, ShrineId,
+        Slope, Surface, Velocity,
+    },
     environment::{Tile, CHUNK_HEIGHT, CHUNK_TILES, CHUNK_WIDTH, TILE_SIZE},
+    ressources::{Placeholder, Placeholders},
+    save::{flags, Flags, SaveData},
     spritesheet::SpriteSheet,
 };
 
+pub const SHRINE_SIZE: (f32, f32) = (20.0, 30.0);
+pub const SHRINE_HEALTH: f32 = 4.0;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GravityZoneData {
+    /// relative to the chunk origin, zones may extend into neighbouring chunks
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    pub zone: GravityZone,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShrineData {
+    /// the bottom left corner of the shrine, relative to the chunk origin
+    pub position: (f32, f32),
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnemyData {
+    /// the bottom left corner of the enemy, relative to the chunk origin
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    pub health: f32,
+    pub behaviour: Behaviour,
+    /// the name of the attack in `GameConfig::enemy_attacks`
+    #[serde(default)]
+    pub attack: Option<String>,
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BossData {
+    /// the bottom left corner of the boss, relative to the chunk origin
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// the bottom left corner of the arena, relative to the chunk origin
+    pub arena: (f32, f32),
+    pub arena_size: (f32, f32),
+    /// the name of the boss in `GameConfig::bosses`
+    pub boss: String,
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CrumblingPlatformData {
+    /// the bottom left corner of the platform, relative to the chunk origin
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// the frames between being stood on and breaking
+    pub delay: usize,
+    /// the frames until the platform is restored
+    pub respawn: usize,
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FallingBlockData {
+    /// the bottom left corner of the block, relative to the chunk origin
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// the maximum distance between the block and the player below it
+    pub trigger_depth: f32,
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SawData {
+    pub size: (f32, f32),
+    pub speed: f32,
+    /// the positions of the bottom left corner of the saw, relative to the chunk origin,
+    /// starting at the first one
+    pub path: Vec<(f32, f32)>,
+    /// the entity is not spawned once this flag is set
+    #[serde(default)]
+    pub hidden_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SurfaceData {
+    /// the position of the tile inside of the chunk
+    pub tile: (usize, usize),
+    pub surface: Surface,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChunkData {
     pub spritesheet: String,
     pub tiles: [[Option<Tile>; CHUNK_TILES]; CHUNK_TILES],
+    /// tiles with a non default `Surface`
+    #[serde(default)]
+    pub surfaces: Vec<SurfaceData>,
+    #[serde(default)]
+    pub gravity_zones: Vec<GravityZoneData>,
+    #[serde(default)]
+    pub shrines: Vec<ShrineData>,
+    #[serde(default)]
+    pub enemies: Vec<EnemyData>,
+    #[serde(default)]
+    pub bosses: Vec<BossData>,
+    #[serde(default)]
+    pub crumbling_platforms: Vec<CrumblingPlatformData>,
+    #[serde(default)]
+    pub falling_blocks: Vec<FallingBlockData>,
+    #[serde(default)]
+    pub saws: Vec<SawData>,
 }
 
 impl Default for ChunkData {
@@ -30,6 +148,14 @@ impl Default for ChunkData {
         ChunkData {
             spritesheet: String::from("textures/grassland.png"),
             tiles: [[None; CHUNK_TILES]; CHUNK_TILES],
+            surfaces: Vec::new(),
+            gravity_zones: Vec::new(),
+            shrines: Vec::new(),
+            enemies: Vec::new(),
+            bosses: Vec::new(),
+            crumbling_platforms: Vec::new(),
+            falling_blocks: Vec::new(),
+            saws: Vec::new(),
         }
     }
 }
@@ -54,7 +180,10 @@ impl ChunkData {
     fn is_solid(&self, x: usize, y: usize) -> bool {
         if x < self.width() && y < self.height() {
             if let Some(tile) = self.tile((x, y)) {
-                tile == Tile::Solid
+                match tile {
+                    Tile::Solid | Tile::Slope(_) => true,
+                    Tile::Grass | Tile::Spike | Tile::Bridge | Tile::Ladder => false,
+                }
             } else {
                 false
             }
@@ -63,6 +192,25 @@ impl ChunkData {
         }
     }
 
+    /// Returns `true` if the tile above `(x, y)` is not a ladder.
+    ///
+    /// Ladders reaching the top of the chunk continue if `above_ladders`,
+    /// the ladders in the bottom row of the chunk above, contains `x`.
+    fn is_ladder_top(&self, x: usize, y: usize, above_ladders: &[usize]) -> bool {
+        if y + 1 < self.height() {
+            self.tile((x, y + 1)) != Some(Tile::Ladder)
+        } else {
+            !above_ladders.contains(&x)
+        }
+    }
+
+    fn surface(&self, (x, y): (usize, usize)) -> Option<Surface> {
+        self.surfaces
+            .iter()
+            .find(|data| data.tile == (x, y))
+            .map(|data| data.surface)
+    }
+
     fn get_spike_sprite_number(&self, x: usize, y: usize) -> usize {
         // TODO: fix spike generation to actually make some kind of sense
         match (x * x * 5).wrapping_sub(y % 11 + 3) % 2 {
@@ -96,6 +244,16 @@ impl ChunkData {
         }
     }
 
+    fn get_ladder_sprite_number(&self, _x: usize, _y: usize) -> usize {
+        70
+    }
+
+    fn get_slope_sprite_number(&self, slope: Slope) -> usize {
+        // the slope sprites are stored directly after the solid sprites,
+        // in the same order as `Slope::all`
+        58 + Slope::all().position(|s| s == slope).unwrap()
+    }
+
     fn get_solid_sprite_number(&self, x: usize, y: usize) -> usize {
         // 6 5 4
         // 7 x 3
@@ -167,6 +325,13 @@ impl ChunkData {
 pub struct Chunk {
     pub position: (i32, i32),
     pub tiles: Vec<Entity>,
+    /// the gravity zones of this chunk, which are also part of `tiles`
+    pub gravity_zones: Vec<Entity>,
+    /// the columns of the ladders in the bottom row
+    pub bottom_ladders: Vec<usize>,
+    /// the tops of the ladders in the top row together with their column,
+    /// which are also part of `tiles`
+    ladder_tops: Vec<(usize, Entity)>,
     #[cfg(feature = "editor")]
     pub data: ChunkData,
     #[cfg(feature = "editor")]
@@ -207,6 +372,9 @@ impl Chunk {
         Chunk {
             position,
             tiles,
+            gravity_zones: Vec::new(),
+            bottom_ladders: Vec::new(),
+            ladder_tops: Vec::new(),
             #[cfg(feature = "editor")]
             data: ChunkData::default(),
             #[cfg(feature = "editor")]
@@ -215,7 +383,13 @@ impl Chunk {
     }
 
     #[cfg(feature = "editor")]
-    pub fn rebuild(&mut self, ctx: &mut Context, c: &mut Components) -> Result<(), crow::Error> {
+    pub fn rebuild(
+        &mut self,
+        ctx: &mut Context,
+        c: &mut Components,
+        above_ladders: &[usize],
+        placeholders: &mut Placeholders,
+    ) -> Result<(), crow::Error> {
         self.clear(c);
 
         let changed = c.new_entity();
@@ -246,10 +420,17 @@ impl Chunk {
 
         for (y, line) in data.tiles.iter().enumerate() {
             for x in 0..line.len() {
-                self.add_tile((x, y), &data, c, &spritesheet);
+                self.add_tile((x, y), &data, above_ladders, c, &spritesheet);
             }
         }
 
+        self.add_gravity_zones(&data, c);
+        // show destroyed shrines, killed enemies and hidden entities while editing
+        self.add_shrines(&data, c, &Flags::default());
+        self.add_enemies(ctx, &data, c, placeholders, &[], &Flags::default())?;
+        self.add_bosses(ctx, &data, c, placeholders, &Flags::default())?;
+        self.add_hazards(ctx, &data, c, placeholders, &Flags::default())?;
+
         Ok(())
     }
 
@@ -257,13 +438,20 @@ impl Chunk {
         ctx: &mut Context,
         position: (i32, i32),
         data: ChunkData,
+        above_ladders: &[usize],
         c: &mut Components,
+        placeholders: &mut Placeholders,
+        last_save: &SaveData,
+        killed_enemies: &[EnemyId],
     ) -> Result<Self, crow::Error> {
         let spritesheet = Self::build_spritesheet(ctx, &data.spritesheet).unwrap();
 
         let mut chunk = Chunk {
             position,
             tiles: Vec::new(),
+            gravity_zones: Vec::new(),
+            bottom_ladders: Vec::new(),
+            ladder_tops: Vec::new(),
             #[cfg(feature = "editor")]
             data: data.clone(),
             #[cfg(feature = "editor")]
@@ -272,17 +460,48 @@ impl Chunk {
 
         for (y, line) in data.tiles.iter().enumerate() {
             for x in 0..line.len() {
-                chunk.add_tile((x, y), &data, c, &spritesheet);
+                chunk.add_tile((x, y), &data, above_ladders, c, &spritesheet);
             }
         }
 
+        chunk.add_gravity_zones(&data, c);
+        chunk.add_shrines(&data, c, &last_save.flags);
+        chunk.add_enemies(
+            ctx,
+            &data,
+            c,
+            placeholders,
+            killed_enemies,
+            &last_save.flags,
+        )?;
+        chunk.add_bosses(ctx, &data, c, placeholders, &last_save.flags)?;
+        chunk.add_hazards(ctx, &data, c, placeholders, &last_save.flags)?;
+
         Ok(chunk)
     }
 
+    /// Removes the tops of the ladders which continue in `above`,
+    /// required if `above` is loaded after this chunk.
+    pub fn continue_ladders(&mut self, above: &Chunk, c: &mut Components) {
+        let tiles = &mut self.tiles;
+        self.ladder_tops.retain(|&(x, top)| {
+            if above.bottom_ladders.contains(&x) {
+                c.delete_entity(top);
+                tiles.retain(|&tile| tile != top);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
     pub fn clear(&mut self, c: &mut Components) {
         for e in self.tiles.drain(..) {
             c.delete_entity(e);
         }
+        self.gravity_zones.clear();
+        self.bottom_ladders.clear();
+        self.ladder_tops.clear();
 
         #[cfg(feature = "editor")]
         {
@@ -300,7 +519,7 @@ impl Chunk {
 
         let mut y = builder.texture.height();
         let mut x = 0;
-        for _ in 0..58 {
+        for _ in 0..71 {
             if x == 0 {
                 y -= 20;
             }
@@ -316,10 +535,372 @@ impl Chunk {
         Ok(builder.finish())
     }
 
+    pub fn add_gravity_zones(&mut self, config: &ChunkData, c: &mut Components) {
+        let (chunk_x, chunk_y) = self.position;
+
+        for zone in config.gravity_zones.iter() {
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            c.positions.insert(
+                entity,
+                Position {
+                    x: (chunk_x * CHUNK_WIDTH as i32) as f32 + zone.position.0,
+                    y: (chunk_y * CHUNK_HEIGHT as i32) as f32 + zone.position.1,
+                },
+            );
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: zone.size.0,
+                    h: zone.size.1,
+                    ty: ColliderType::GravityZone,
+                },
+            );
+            c.gravity_zones.insert(entity, zone.zone);
+            self.gravity_zones.push(entity);
+        }
+    }
+
+    pub fn add_shrines(&mut self, config: &ChunkData, c: &mut Components, flags: &Flags) {
+        let (chunk_x, chunk_y) = self.position;
+
+        for (index, shrine) in config.shrines.iter().enumerate() {
+            let id = ShrineId {
+                chunk: self.position,
+                index,
+            };
+            if flags.is_set(&flags::destroyed_shrine(id)) || is_hidden(&shrine.hidden_by, flags) {
+                continue;
+            }
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            let position = Position {
+                x: (chunk_x * CHUNK_WIDTH as i32) as f32 + shrine.position.0,
+                y: (chunk_y * CHUNK_HEIGHT as i32) as f32 + shrine.position.1,
+            };
+            c.positions.insert(entity, position);
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: SHRINE_SIZE.0,
+                    h: SHRINE_SIZE.1,
+                    ty: ColliderType::Shrine,
+                },
+            );
+            c.shrines.insert(
+                entity,
+                Shrine {
+                    id,
+                    spawn: position,
+                },
+            );
+            c.healths.insert(entity, Health(SHRINE_HEALTH));
+        }
+    }
+
+    pub fn add_enemies(
+        &mut self,
+        ctx: &mut Context,
+        config: &ChunkData,
+        c: &mut Components,
+        placeholders: &mut Placeholders,
+        killed: &[EnemyId],
+        flags: &Flags,
+    ) -> Result<(), crow::Error> {
+        let (chunk_x, chunk_y) = self.position;
+
+        for (index, enemy) in config.enemies.iter().enumerate() {
+            let id = EnemyId {
+                chunk: self.position,
+                index,
+            };
+            if killed.contains(&id) || is_hidden(&enemy.hidden_by, flags) {
+                continue;
+            }
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            let position = Position {
+                x: (chunk_x * CHUNK_WIDTH as i32) as f32 + enemy.position.0,
+                y: (chunk_y * CHUNK_HEIGHT as i32) as f32 + enemy.position.1,
+            };
+            let velocity = Velocity { x: 0.0, y: 0.0 };
+            c.positions.insert(entity, position);
+            c.velocities.insert(entity, velocity);
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: enemy.size.0,
+                    h: enemy.size.1,
+                    ty: ColliderType::Enemy,
+                },
+            );
+            c.gravity.insert(entity, Gravity::default());
+            c.healths.insert(entity, Health(enemy.health));
+            c.factions.insert(entity, Faction::Enemy);
+            c.enemies.insert(
+                entity,
+                Enemy {
+                    id,
+                    post: position,
+                    health: enemy.health,
+                },
+            );
+            c.behaviours.insert(entity, enemy.behaviour);
+            c.behaviour_states.insert(entity, BehaviourState::default());
+            if let Some(attack) = enemy.attack.clone() {
+                c.attackers.insert(
+                    entity,
+                    Attacker {
+                        attack,
+                        cooldown: 0,
+                        current: None,
+                    },
+                );
+            }
+            c.resettables
+                .insert(entity, Resettable { position, velocity });
+            c.depths.insert(entity, Depth::Player);
+
+            c.sprites.insert(
+                entity,
+                placeholders.sprite(ctx, Placeholder::Enemy, enemy.size)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn add_bosses(
+        &mut self,
+        ctx: &mut Context,
+        config: &ChunkData,
+        c: &mut Components,
+        placeholders: &mut Placeholders,
+        flags: &Flags,
+    ) -> Result<(), crow::Error> {
+        let (chunk_x, chunk_y) = self.position;
+        let origin = Position {
+            x: (chunk_x * CHUNK_WIDTH as i32) as f32,
+            y: (chunk_y * CHUNK_HEIGHT as i32) as f32,
+        };
+
+        for (index, boss) in config.bosses.iter().enumerate() {
+            let id = BossId {
+                chunk: self.position,
+                index,
+            };
+            if flags.is_set(&flags::defeated_boss(id)) || is_hidden(&boss.hidden_by, flags) {
+                continue;
+            }
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            let position = Position {
+                x: origin.x + boss.position.0,
+                y: origin.y + boss.position.1,
+            };
+            let velocity = Velocity { x: 0.0, y: 0.0 };
+            c.positions.insert(entity, position);
+            c.velocities.insert(entity, velocity);
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: boss.size.0,
+                    h: boss.size.1,
+                    ty: ColliderType::Enemy,
+                },
+            );
+            c.gravity.insert(entity, Gravity::default());
+            c.factions.insert(entity, Faction::Enemy);
+            c.bosses.insert(
+                entity,
+                Boss {
+                    id,
+                    name: boss.boss.clone(),
+                    arena: Position {
+                        x: origin.x + boss.arena.0,
+                        y: origin.y + boss.arena.1,
+                    },
+                    arena_size: boss.arena_size,
+                    state: BossState::Waiting,
+                    phase: 0,
+                    next_attack: 0,
+                    spawned: Vec::new(),
+                },
+            );
+            c.resettables
+                .insert(entity, Resettable { position, velocity });
+            c.depths.insert(entity, Depth::Player);
+
+            c.sprites.insert(
+                entity,
+                placeholders.sprite(ctx, Placeholder::Boss, boss.size)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn add_hazards(
+        &mut self,
+        ctx: &mut Context,
+        config: &ChunkData,
+        c: &mut Components,
+        placeholders: &mut Placeholders,
+        flags: &Flags,
+    ) -> Result<(), crow::Error> {
+        let (chunk_x, chunk_y) = self.position;
+        let origin = Position {
+            x: (chunk_x * CHUNK_WIDTH as i32) as f32,
+            y: (chunk_y * CHUNK_HEIGHT as i32) as f32,
+        };
+        let velocity = Velocity { x: 0.0, y: 0.0 };
+
+        for platform in config.crumbling_platforms.iter() {
+            if is_hidden(&platform.hidden_by, flags) {
+                continue;
+            }
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            let (w, h) = platform.size;
+            let sprite = placeholders.sprite(ctx, Placeholder::CrumblingPlatform, platform.size)?;
+
+            c.positions.insert(
+                entity,
+                Position {
+                    x: origin.x + platform.position.0,
+                    y: origin.y + platform.position.1,
+                },
+            );
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w,
+                    h,
+                    ty: ColliderType::Environment,
+                },
+            );
+            c.crumbling_platforms.insert(
+                entity,
+                CrumblingPlatform {
+                    size: platform.size,
+                    delay: platform.delay,
+                    respawn: platform.respawn,
+                    state: CrumbleState::Intact,
+                    sprite: sprite.clone(),
+                },
+            );
+            c.depths.insert(entity, Depth::Tiles);
+            c.sprites.insert(entity, sprite);
+        }
+
+        for block in config.falling_blocks.iter() {
+            if is_hidden(&block.hidden_by, flags) {
+                continue;
+            }
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            let position = Position {
+                x: origin.x + block.position.0,
+                y: origin.y + block.position.1,
+            };
+            c.positions.insert(entity, position);
+            c.velocities.insert(entity, velocity);
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: block.size.0,
+                    h: block.size.1,
+                    ty: ColliderType::Environment,
+                },
+            );
+            c.falling_blocks.insert(
+                entity,
+                FallingBlock {
+                    trigger_depth: block.trigger_depth,
+                    state: FallingState::Waiting,
+                },
+            );
+            c.resettables
+                .insert(entity, Resettable { position, velocity });
+            c.depths.insert(entity, Depth::Tiles);
+
+            c.sprites.insert(
+                entity,
+                placeholders.sprite(ctx, Placeholder::FallingBlock, block.size)?,
+            );
+        }
+
+        for saw in config.saws.iter() {
+            if is_hidden(&saw.hidden_by, flags) {
+                continue;
+            }
+
+            let path: Vec<_> = saw
+                .path
+                .iter()
+                .map(|&(x, y)| Position {
+                    x: origin.x + x,
+                    y: origin.y + y,
+                })
+                .collect();
+            let position = match path.first() {
+                Some(&position) => position,
+                None => {
+                    warn!("Saw without a path in chunk {:?}", self.position);
+                    continue;
+                }
+            };
+
+            let entity = c.new_entity();
+            self.tiles.push(entity);
+
+            c.positions.insert(entity, position);
+            c.velocities.insert(entity, velocity);
+            c.colliders.insert(
+                entity,
+                Collider {
+                    w: saw.size.0,
+                    h: saw.size.1,
+                    ty: ColliderType::PlayerDamage,
+                },
+            );
+            c.saws.insert(
+                entity,
+                Saw {
+                    path,
+                    speed: saw.speed,
+                    target: 0,
+                },
+            );
+            c.resettables
+                .insert(entity, Resettable { position, velocity });
+            c.depths.insert(entity, Depth::Player);
+
+            c.sprites.insert(
+                entity,
+                placeholders.sprite(ctx, Placeholder::Saw, saw.size)?,
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn add_tile(
         &mut self,
         (x, y): (usize, usize),
         config: &ChunkData,
+        above_ladders: &[usize],
         c: &mut Components,
         sheet: &SpriteSheet,
     ) {
@@ -359,6 +940,50 @@ impl Chunk {
                         },
                     );
                 }
+                Tile::Ladder => {
+                    c.colliders.insert(
+                        entity,
+                        Collider {
+                            w: 20.0,
+                            h: 20.0,
+                            ty: ColliderType::Ladder,
+                        },
+                    );
+
+                    if y == 0 {
+                        self.bottom_ladders.push(x);
+                    }
+
+                    if config.is_ladder_top(x, y, above_ladders) {
+                        // the top of a ladder can be stood upon like a bridge
+                        let top = c.new_entity();
+                        self.tiles.push(top);
+                        c.positions.insert(top, *c.positions.get(entity).unwrap());
+                        c.colliders.insert(
+                            top,
+                            Collider {
+                                w: 20.0,
+                                h: 20.0,
+                                ty: ColliderType::Bridge,
+                            },
+                        );
+
+                        if y + 1 == config.height() {
+                            self.ladder_tops.push((x, top));
+                        }
+                    }
+                }
+                Tile::Slope(slope) => {
+                    c.colliders.insert(
+                        entity,
+                        Collider {
+                            w: 20.0,
+                            h: 20.0,
+                            ty: ColliderType::Environment,
+                        },
+                    );
+                    c.slopes.insert(entity, slope);
+                }
                 Tile::Spike => {
                     c.colliders.insert(
                         entity,
@@ -372,6 +997,10 @@ impl Chunk {
                 Tile::Grass => (),
             }
 
+            if let Some(surface) = config.surface((x, y)) {
+                c.surfaces.insert(entity, surface);
+            }
+
             c.depths.insert(entity, tile.depth());
 
             c.sprites.insert(
@@ -381,8 +1010,15 @@ impl Chunk {
                     Tile::Solid => config.get_solid_sprite_number(x, y),
                     Tile::Grass => config.get_grass_sprite_number(x, y),
                     Tile::Spike => config.get_spike_sprite_number(x, y),
+                    Tile::Ladder => config.get_ladder_sprite_number(x, y),
+                    Tile::Slope(slope) => config.get_slope_sprite_number(slope),
                 }),
             );
         }
     }
 }
+
+/// Returns `true` if an entity with the given `hidden_by` flag should not be spawned.
+fn is_hidden(hidden_by: &Option<String>, flags: &Flags) -> bool {
+    hidden_by.as_ref().map_or(false, |flag| flags.is_set(flag))
+}
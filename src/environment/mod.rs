@@ -10,8 +10,9 @@ use crow_ecs::Joinable;
 use crate::config::StoreError;
 use crate::{
     config::Config,
-    data::{Components, Depth},
+    data::{Components, Depth, EnemyId, Slope},
     ressources::Ressources,
+    save::flags,
 };
 
 pub mod chunk;
@@ -31,6 +32,8 @@ pub enum Tile {
     Grass,
     Spike,
     Bridge,
+    Ladder,
+    Slope(Slope),
 }
 
 impl Default for Tile {
@@ -42,8 +45,8 @@ impl Default for Tile {
 impl Tile {
     fn depth(self) -> Depth {
         match self {
-            Tile::Solid => Depth::Tiles,
-            Tile::Bridge => Depth::Bridges,
+            Tile::Solid | Tile::Slope(_) => Depth::Tiles,
+            Tile::Bridge | Tile::Ladder => Depth::Bridges,
             Tile::Grass => Depth::Grass,
             Tile::Spike => Depth::Grass,
         }
@@ -58,6 +61,8 @@ pub struct WorldData {
 pub struct World {
     pub data: WorldData,
     pub chunks: Vec<Chunk>,
+    /// enemies which are not spawned until the player respawns
+    pub killed_enemies: Vec<EnemyId>,
 }
 
 impl World {
@@ -65,13 +70,10 @@ impl World {
         World {
             data,
             chunks: Vec::new(),
+            killed_enemies: Vec::new(),
         }
     }
 
-    pub fn reset(&mut self) {
-        self.chunks.clear();
-    }
-
     #[cfg(feature = "editor")]
     pub fn save(&mut self, c: &mut Components) -> Result<(), StoreError> {
         for chunk in self.chunks.iter_mut() {
@@ -160,9 +162,39 @@ impl EnvironmentSystem {
         profile_scope!("load_chunk");
 
         if let Some(path) = r.world.data.chunks.get(&position) {
-            let config = ChunkData::load(path).unwrap();
+            // stored together with the next save
+            r.last_save.flags.set(flags::visited_chunk(position), 1);
 
-            let chunk = Chunk::new(ctx, position, config, c)?;
+            let config = ChunkData::load(path).unwrap();
+            // the tops of ladders at the chunk borders depend on the chunks above and below
+            let above = (position.0, position.1 + 1);
+            let above_ladders = r
+                .world
+                .chunks
+                .iter()
+                .find(|chunk| chunk.position == above)
+                .map_or(&[][..], |chunk| &chunk.bottom_ladders);
+
+            let chunk = Chunk::new(
+                ctx,
+                position,
+                config,
+                above_ladders,
+                c,
+                &mut r.placeholders,
+                &r.last_save,
+                &r.world.killed_enemies,
+            )?;
+
+            let below = (position.0, position.1 - 1);
+            if let Some(below) = r
+                .world
+                .chunks
+                .iter_mut()
+                .find(|loaded| loaded.position == below)
+            {
+                below.continue_ladders(&chunk, c);
+            }
             r.world.chunks.push(chunk);
         } else {
             let chunk = Chunk::empty(position, c);
@@ -1,12 +1,18 @@
+use std::mem;
+
 use crow::Context;
 
+use crow_ecs::{Entities, Joinable};
+
 use crate::{
-    config::{Config, PlayerAnimationsConfig},
+    config::{AnimationControllerConfig, Config},
     data::{
-        Camera, Collider, ColliderType, Components, Depth, Gravity, PlayerAnimations, PlayerState,
-        Position, Velocity,
+        AnimationController, BehaviourState, Camera, Collider, ColliderType, Components, Depth,
+        Enemy, Faction, Gravity, Health, ModifierSource, PlayerState, Position, Resettable, Stat,
+        StatModifiers, Velocity,
     },
     ressources::Ressources,
+    systems,
 };
 
 pub fn player(
@@ -30,23 +36,134 @@ pub fn player(
     );
 
     c.velocities.insert(player, Velocity { x: 0.0, y: 0.0 });
-    c.gravity.insert(player, Gravity);
+    c.gravity.insert(player, Gravity::default());
     c.player_state.insert(player, PlayerState::Grounded);
+    c.factions.insert(player, Faction::Player);
     c.depths.insert(player, Depth::Player);
 
-    let player_animations = PlayerAnimations::from_config(
+    let mut modifiers = StatModifiers::default();
+    modifiers.set(
+        ModifierSource::GoddessPower,
+        Stat::AttackDamage,
+        r.last_save.attack_factor(&r.config.goddess),
+    );
+    c.stat_modifiers.insert(player, modifiers);
+
+    let controller = AnimationController::from_config(
         ctx,
         &mut r.animation_storage,
-        PlayerAnimationsConfig::load("ressources/player/animations.ron").unwrap(),
+        AnimationControllerConfig::load("ressources/player/animations.ron").unwrap(),
     )?;
 
     c.animations
-        .insert(player, r.animation_storage.start(player_animations.idle));
-    c.player_animations.insert(player, player_animations);
+        .insert(player, r.animation_storage.start(controller.initial));
+    c.animation_controllers.insert(player, controller);
 
     Ok(())
 }
 
+/// Moves the player back to the last save and resets all `Resettable` entities.
+///
+/// In contrast to rebuilding the world using `player`, this keeps
+/// all loaded chunks and textures.
+pub fn respawn(c: &mut Components, r: &mut Ressources) {
+    #[cfg(feature = "profiler")]
+    profile_scope!("respawn");
+
+    let players: Vec<_> = (&c.player_state, Entities)
+        .join()
+        .map(|(_, entity)| entity)
+        .collect();
+
+    for player in players {
+        if let Some(hitbox) = c.attacks.get(player).and_then(|attack| attack.hitbox) {
+            c.delete_entity(hitbox);
+        }
+
+        c.positions.insert(player, r.last_save.position);
+        c.previous_positions.remove(player);
+        c.velocities.insert(player, Velocity { x: 0.0, y: 0.0 });
+        c.player_state.insert(player, PlayerState::Grounded);
+        c.grounded.remove(player);
+        c.wall_collisions.remove(player);
+        c.ignore_bridges.remove(player);
+        c.ignore_gravity.remove(player);
+        c.drop_throughs.remove(player);
+        c.rolls.remove(player);
+        c.attacks.remove(player);
+        c.ledge_grabs.remove(player);
+        c.roll_cooldowns.remove(player);
+        c.coyote_time.remove(player);
+        c.input_locks.remove(player);
+        c.jumping.remove(player);
+        c.mirrored.remove(player);
+
+        if let Some(controller) = c.animation_controllers.get(player) {
+            c.animations
+                .insert(player, r.animation_storage.start(controller.initial));
+        }
+    }
+
+    let cameras: Vec<_> = (&c.cameras, Entities)
+        .join()
+        .map(|(_, entity)| entity)
+        .collect();
+
+    for camera in cameras {
+        let (offset_x, offset_y) = r.config.camera.offset;
+        c.positions.insert(
+            camera,
+            Position {
+                x: r.last_save.position.x - offset_x,
+                y: r.last_save.position.y - offset_y,
+            },
+        );
+        c.previous_positions.remove(camera);
+        c.velocities.insert(camera, Velocity { x: 0.0, y: 0.0 });
+    }
+
+    let resettables: Vec<_> = (&c.resettables, Entities)
+        .join()
+        .map(|(&resettable, entity)| (resettable, entity))
+        .collect();
+
+    for (Resettable { position, velocity }, entity) in resettables {
+        c.positions.insert(entity, position);
+        c.previous_positions.remove(entity);
+        c.velocities.insert(entity, velocity);
+    }
+
+    let enemies: Vec<_> = (&c.enemies, Entities)
+        .join()
+        .map(|(&enemy, entity)| (enemy, entity))
+        .collect();
+
+    for (Enemy { health, .. }, enemy) in enemies {
+        systems::cancel_attack(enemy, c, &mut r.world);
+        c.healths.insert(enemy, Health(health));
+        c.behaviour_states.insert(enemy, BehaviourState::default());
+        c.mirrored.remove(enemy);
+    }
+
+    systems::reset_bosses(c, r);
+
+    systems::reset_hazards(c);
+
+    r.projectiles.clear(c);
+
+    // reload all chunks with killed enemies, which respawns them
+    let killed = mem::replace(&mut r.world.killed_enemies, Vec::new());
+    for i in (0..r.world.chunks.len()).rev() {
+        let position = r.world.chunks[i].position;
+        if killed.iter().any(|enemy| enemy.chunk == position) {
+            r.world.chunks.swap_remove(i).clear(c);
+        }
+    }
+
+    r.fadeout = None;
+    r.input_buffer.clear();
+}
+
 pub fn camera(c: &mut Components, r: &mut Ressources) {
     let camera = c.new_entity();
 
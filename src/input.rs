@@ -12,6 +12,27 @@ pub enum InputEvent {
     MouseUp(MouseButton),
 }
 
+/// A logical action of the player which can be buffered by the `InputBufferSystem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    Jump,
+    Roll,
+    Attack,
+    /// drop through bridges
+    Drop,
+}
+
+impl InputAction {
+    pub fn all() -> &'static [InputAction] {
+        &[
+            InputAction::Jump,
+            InputAction::Roll,
+            InputAction::Attack,
+            InputAction::Drop,
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyState {
     Down,
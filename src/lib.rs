@@ -187,6 +187,7 @@ pub fn editor_frame(
         &c.sprites,
         &c.depths,
         &c.mirrored,
+        &c.flashes,
         &c.colliders,
         &c.cameras,
     )?;
@@ -207,7 +208,7 @@ pub fn game_frame(
 ) -> Result<bool, crow::Error> {
     s.input_buffer.run(
         r.input_state.events(),
-        &mut r.pressed_space,
+        &mut r.input_buffer,
         &r.config.input_buffer,
         &r.config.input,
     );
@@ -217,31 +218,61 @@ pub fn game_frame(
         &c.positions,
         &c.previous_positions,
         &mut c.velocities,
+        &c.colliders,
         &c.cameras,
+        r.camera_lock,
         &r.time,
         &r.config.camera,
     );
 
-    s.gravity
-        .run(&c.gravity, &mut c.velocities, &r.time, &r.config.gravity);
+    s.gravity.run(
+        &c.gravity,
+        &c.gravity_zones,
+        &c.ignore_gravity,
+        &c.positions,
+        &c.colliders,
+        &mut c.velocities,
+        &r.world,
+        &r.time,
+        &r.config.gravity,
+    );
 
     let mut collisions = s.physics.run(
         &c.velocities,
         &c.colliders,
+        &c.slopes,
         &mut c.previous_positions,
         &mut c.positions,
         &mut c.grounded,
         &r.time,
+        &r.config.collision,
     );
 
+    let trigger_events = s
+        .trigger
+        .run(&c.positions, &c.colliders, &r.config.collision);
+
     s.bridge_collision.run(
-        &c.positions,
+        &mut c.positions,
         &c.previous_positions,
         &c.colliders,
+        &mut c.velocities,
+        &mut c.grounded,
         &c.ignore_bridges,
+        &mut c.drop_throughs,
         &mut collisions,
     );
 
+    s.slope_collision.run(
+        &mut c.positions,
+        &mut c.grounded,
+        &mut c.velocities,
+        &c.colliders,
+        &c.slopes,
+        &mut collisions,
+        &r.time,
+    );
+
     s.fixed_collision.run(
         &mut c.positions,
         &c.previous_positions,
@@ -252,12 +283,44 @@ pub fn game_frame(
         &collisions,
     );
 
-    s.player.run(c, r, &collisions);
+    s.projectile.run(c, r, &collisions);
+
+    s.hazard.run(c, r);
+
+    s.player.run(c, r, &collisions, s.fixed_collision.crushed());
+
+    s.hitbox.run(
+        &c.positions,
+        &c.colliders,
+        &mut c.hitboxes,
+        &mut c.healths,
+        &r.config.collision,
+    );
+
+    s.shrine.run(trigger_events, c, r);
+
+    s.enemy.run(c, r);
+
+    s.boss.run(c, r);
+
+    s.enemy_attack.run(ctx, c, r)?;
 
     s.environment.run(ctx, c, r)?;
 
     s.fadeout.run(&mut r.fadeout);
 
+    s.animation_controller.run(
+        &c.animation_controllers,
+        &mut c.animations,
+        &c.velocities,
+        &c.grounded,
+        &c.wall_collisions,
+        &c.player_state,
+        &c.attacks,
+        &c.attackers,
+        &r.animation_storage,
+    );
+
     s.animation
         .run(&mut c.sprites, &mut c.animations, &mut r.animation_storage);
 
@@ -270,6 +333,7 @@ pub fn game_frame(
         &c.sprites,
         &c.depths,
         &c.mirrored,
+        &c.flashes,
         &c.colliders,
         &c.cameras,
     )?;
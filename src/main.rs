@@ -15,8 +15,9 @@ fn main() -> Result<(), crow::Error> {
     thread_profiler::register_thread_with_profiler();
 
     let config = GameConfig::load("ressources/game_config.ron").unwrap();
+    config.validate().unwrap();
     let world_data = WorldData::load("ressources/environment/world.ron").unwrap();
-    let save_data = SaveData::load("ressources/save/test_save.ron").unwrap();
+    let save_data = SaveData::load_current().unwrap();
     let mut game = GlobalState::new(config, world_data, save_data)?;
 
     init::player(&mut game.ctx, &mut game.c, &mut game.r)?;
@@ -1,12 +1,39 @@
 use std::convert::TryFrom;
 
-use crate::data::{Collider, CollisionDirection, Position, Velocity};
+use crow_ecs::{Entities, Entity, Joinable, SparseStorage, Storage};
+
+use crate::{
+    config::CollisionConfig,
+    data::{Collider, ColliderType, CollisionDirection, Position, Slope, Velocity},
+    time::Time,
+};
+
+/// The height above the ground at which `at_ledge` starts looking for ground.
+const GROUND_PROBE_OFFSET: f32 = 1.0;
 
 pub fn is_collision(a: Position, a_col: Collider, b: Position, b_col: Collider) -> bool {
-    let (a_w, a_h) = (a.x + a_col.w, a.y + a_col.h);
-    let (b_w, b_h) = (b.x + b_col.w, b.y + b_col.h);
+    is_overlap(a, (a_col.w, a_col.h), b, b_col)
+}
+
+/// Returns `true` if the area of size `(w, h)` at `pos` overlaps the collider `other_col`.
+pub fn is_overlap(
+    pos: Position,
+    (w, h): (f32, f32),
+    other_pos: Position,
+    other_col: Collider,
+) -> bool {
+    let (a_w, a_h) = (pos.x + w, pos.y + h);
+    let (b_w, b_h) = (other_pos.x + other_col.w, other_pos.y + other_col.h);
 
-    a.x < b_w && a_w > b.x && a.y < b_h && a_h > b.y
+    pos.x < b_w && a_w > other_pos.x && pos.y < b_h && a_h > other_pos.y
+}
+
+/// Returns `true` if `point` is inside of the collider `col` at `pos`.
+pub fn contains(pos: Position, col: &Collider, point: Position) -> bool {
+    col.left_border(pos) <= point.x
+        && point.x < col.right_border(pos)
+        && col.lower_border(pos) <= point.y
+        && point.y < col.upper_border(pos)
 }
 
 /// get the direction of the collision
@@ -33,3 +60,175 @@ pub fn collision_direction(
 
     CollisionDirection::try_from(vertical as u8 | horizontal as u8).unwrap()
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct RaycastHit {
+    pub entity: Entity,
+    pub point: Position,
+    /// the surface normal at `point`, has a length of 1
+    pub normal: (f32, f32),
+    pub distance: f32,
+}
+
+/// The outline of a collider in counterclockwise order, respecting the shape of slopes.
+fn collider_polygon(pos: Position, col: &Collider, slope: Option<Slope>) -> [(f32, f32); 4] {
+    let (x, y) = (col.left_border(pos), col.lower_border(pos));
+    let (xw, yh) = (col.right_border(pos), col.upper_border(pos));
+    match slope {
+        None => [(x, y), (xw, y), (xw, yh), (x, yh)],
+        Some(slope) => {
+            let (left, right) = (slope.height(0.0, col), slope.height(col.w, col));
+            if slope.ceiling {
+                [(x, yh - left), (xw, yh - right), (xw, yh), (x, yh)]
+            } else {
+                [(x, y), (xw, y), (xw, y + right), (x, y + left)]
+            }
+        }
+    }
+}
+
+/// Returns the distance and normal of the first intersection of a ray with a convex polygon.
+///
+/// Rays starting inside of the polygon do not intersect it.
+fn ray_polygon(
+    origin: Position,
+    direction: (f32, f32),
+    polygon: &[(f32, f32)],
+) -> Option<(f32, (f32, f32))> {
+    let mut enter: Option<(f32, (f32, f32))> = None;
+    let mut exit = std::f32::INFINITY;
+    for (i, &(ax, ay)) in polygon.iter().enumerate() {
+        let (bx, by) = polygon[(i + 1) % polygon.len()];
+        // outward normal of a counterclockwise edge
+        let normal = (by - ay, ax - bx);
+        if normal == (0.0, 0.0) {
+            continue;
+        }
+
+        let distance = normal.0 * (ax - origin.x) + normal.1 * (ay - origin.y);
+        let speed = normal.0 * direction.0 + normal.1 * direction.1;
+        if speed == 0.0 {
+            if distance < 0.0 {
+                // parallel to and outside of this edge
+                return None;
+            }
+        } else if speed < 0.0 {
+            let t = distance / speed;
+            if enter.map_or(true, |(enter, _)| t > enter) {
+                enter = Some((t, normal));
+            }
+        } else {
+            exit = exit.min(distance / speed);
+        }
+    }
+
+    match enter {
+        Some((t, (nx, ny))) if t >= 0.0 && t <= exit => {
+            let len = (nx * nx + ny * ny).sqrt();
+            Some((t, (nx / len, ny / len)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the closest collider of one of the given `layers` hit by a ray.
+///
+/// `direction` does not have to be normalized. Colliders containing
+/// `origin` are ignored.
+pub fn raycast(
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    slopes: &SparseStorage<Slope>,
+    origin: Position,
+    direction: (f32, f32),
+    max_distance: f32,
+    layers: &[ColliderType],
+) -> Option<RaycastHit> {
+    let len = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+    if len == 0.0 {
+        return None;
+    }
+    let direction = (direction.0 / len, direction.1 / len);
+
+    let mut closest: Option<RaycastHit> = None;
+    for (&pos, col, slope, entity) in (positions, colliders, slopes.maybe(), Entities).join() {
+        if !layers.contains(&col.ty) {
+            continue;
+        }
+
+        let polygon = collider_polygon(pos, col, slope.copied());
+        if let Some((distance, normal)) = ray_polygon(origin, direction, &polygon) {
+            if distance <= max_distance && closest.map_or(true, |c| distance < c.distance) {
+                closest = Some(RaycastHit {
+                    entity,
+                    point: Position {
+                        x: origin.x + direction.0 * distance,
+                        y: origin.y + direction.1 * distance,
+                    },
+                    normal,
+                    distance,
+                });
+            }
+        }
+    }
+
+    closest
+}
+
+/// Returns all colliders of one of the given `layers` overlapping the area
+/// of size `(w, h)` at `pos`.
+///
+/// Slopes are treated as boxes.
+pub fn overlapping<'a>(
+    positions: &'a Storage<Position>,
+    colliders: &'a Storage<Collider>,
+    pos: Position,
+    (w, h): (f32, f32),
+    layers: &'a [ColliderType],
+) -> impl Iterator<Item = Entity> + 'a {
+    (positions, colliders, Entities)
+        .join()
+        .filter(move |&(&other_pos, &other_col, _)| {
+            layers.contains(&other_col.ty) && is_overlap(pos, (w, h), other_pos, other_col)
+        })
+        .map(|(_, _, entity)| entity)
+}
+
+/// Returns `true` if there is no ground in front of `entity` after its next movement.
+pub fn at_ledge(
+    entity: Entity,
+    velocity: &Velocity,
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    slopes: &SparseStorage<Slope>,
+    time: &Time,
+    config: &CollisionConfig,
+) -> bool {
+    let (position, collider) = match (positions.get(entity), colliders.get(entity)) {
+        (Some(&position), Some(&collider)) => (position, collider),
+        _ => return false,
+    };
+
+    let step = velocity.x * time.fixed_seconds();
+    let front = if step < 0.0 {
+        collider.left_border(position) + step
+    } else {
+        collider.right_border(position) + step
+    };
+
+    // start slightly above the ground, as colliders containing the origin are ignored
+    let origin = Position {
+        x: front,
+        y: position.y + GROUND_PROBE_OFFSET,
+    };
+    raycast(
+        positions,
+        colliders,
+        slopes,
+        origin,
+        (0.0, -1.0),
+        GROUND_PROBE_OFFSET * 2.0 + step.abs(),
+        &config.ground_layers(collider.ty),
+    )
+    .is_none()
+}
@@ -1,12 +1,16 @@
-use crow::Context;
+use std::collections::HashMap;
 
-use crow_anim::AnimationStorage;
+use crow::{Context, Texture};
+
+use crow_anim::{AnimationStorage, Sprite};
+
+use crow_ecs::Entity;
 
 use crate::{
-    config::GameConfig,
-    data::Components,
+    config::{GameConfig, ProjectileConfig},
+    data::{Collider, ColliderType, Components, Depth, Gravity, Position, Projectile, Velocity},
     environment::{World, WorldData},
-    input::InputState,
+    input::{InputAction, InputState},
     save::SaveData,
     systems::Systems,
     time::Time,
@@ -16,10 +20,14 @@ pub struct Ressources {
     pub input_state: InputState,
     pub time: Time,
     pub config: GameConfig,
-    pub pressed_space: Option<JumpBuffer>,
+    pub input_buffer: InputBuffer,
     pub animation_storage: AnimationStorage,
     pub world: World,
     pub fadeout: Option<Fadeout>,
+    /// restricts the camera to an area, used during boss fights
+    pub camera_lock: Option<CameraLock>,
+    pub projectiles: ProjectilePool,
+    pub placeholders: Placeholders,
     pub delayed_actions: Vec<DelayedAction>,
     pub last_save: SaveData,
     pub debug_draw: bool,
@@ -31,24 +39,214 @@ impl Ressources {
             input_state: InputState::new(),
             time: Time::new(config.fps),
             config,
-            pressed_space: None,
+            input_buffer: InputBuffer::new(),
             animation_storage: AnimationStorage::new(),
             world: World::new(world_data),
             fadeout: None,
+            camera_lock: None,
+            projectiles: ProjectilePool::new(),
+            placeholders: Placeholders::new(),
             delayed_actions: Vec::new(),
             last_save,
             debug_draw: false,
         }
     }
+}
+
+/// Recently pressed actions together with the remaining frames until they expire.
+#[derive(Debug, Default, Clone)]
+pub struct InputBuffer {
+    buffered: Vec<(InputAction, u8)>,
+}
+
+impl InputBuffer {
+    pub fn new() -> Self {
+        Default::default()
+    }
 
-    pub fn reset(&mut self) {
-        self.fadeout = None;
-        self.delayed_actions.clear();
-        self.world.reset();
+    /// Buffers `action` for `frames`, replacing a previous press of the same action.
+    pub fn press(&mut self, action: InputAction, frames: u8) {
+        self.buffered.retain(|&(a, _)| a != action);
+        self.buffered.push((action, frames));
     }
+
+    /// Advances the buffer by one frame, removing expired actions.
+    pub fn update(&mut self) {
+        self.buffered.retain(|&(_, frames)| frames > 0);
+        for (_, frames) in self.buffered.iter_mut() {
+            *frames -= 1;
+        }
+    }
+
+    pub fn is_buffered(&self, action: InputAction) -> bool {
+        self.buffered.iter().any(|&(a, _)| a == action)
+    }
+
+    /// Removes `action` from the buffer, returning `true` if it was buffered.
+    ///
+    /// Actions should be consumed once they are used, preventing
+    /// a single key press from being used multiple times.
+    pub fn consume(&mut self, action: InputAction) -> bool {
+        if let Some(idx) = self.buffered.iter().position(|&(a, _)| a == action) {
+            self.buffered.remove(idx);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buffered.clear();
+    }
+}
+
+/// Reuses the entities of removed projectiles, preventing
+/// bullet patterns from constantly creating new entities.
+#[derive(Debug, Default)]
+pub struct ProjectilePool {
+    free: Vec<Entity>,
+    active: Vec<Entity>,
 }
 
-pub struct JumpBuffer(pub u8);
+impl ProjectilePool {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn spawn(
+        &mut self,
+        ctx: &mut Context,
+        c: &mut Components,
+        placeholders: &mut Placeholders,
+        config: &ProjectileConfig,
+        position: Position,
+        velocity: Velocity,
+    ) -> Result<Entity, crow::Error> {
+        let entity = match self.free.pop() {
+            Some(entity) => entity,
+            None => c.new_entity(),
+        };
+        self.active.push(entity);
+
+        c.positions.insert(entity, position);
+        c.velocities.insert(entity, velocity);
+        c.colliders.insert(
+            entity,
+            Collider {
+                w: config.size.0,
+                h: config.size.1,
+                ty: ColliderType::Projectile,
+            },
+        );
+        if let Some(scale) = config.gravity {
+            c.gravity.insert(
+                entity,
+                Gravity {
+                    scale,
+                    terminal_velocity: None,
+                },
+            );
+        }
+        c.projectiles.insert(
+            entity,
+            Projectile {
+                lifetime: config.lifetime,
+                on_hit: config.on_hit,
+            },
+        );
+        c.depths.insert(entity, Depth::Player);
+
+        c.sprites.insert(
+            entity,
+            placeholders.sprite(ctx, Placeholder::Projectile, config.size)?,
+        );
+
+        Ok(entity)
+    }
+
+    /// Removes the projectile `entity`, keeping it for later use.
+    pub fn release(&mut self, c: &mut Components, entity: Entity) {
+        if let Some(idx) = self.active.iter().position(|&e| e == entity) {
+            self.active.swap_remove(idx);
+            c.remove_components(entity);
+            self.free.push(entity);
+        }
+    }
+
+    /// Removes all active projectiles.
+    pub fn clear(&mut self, c: &mut Components) {
+        for entity in self.active.drain(..) {
+            c.remove_components(entity);
+            self.free.push(entity);
+        }
+    }
+}
+
+/// Entities which are drawn as a solid colored rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placeholder {
+    Enemy,
+    Boss,
+    CrumblingPlatform,
+    FallingBlock,
+    Saw,
+    Projectile,
+}
+
+impl Placeholder {
+    fn color(self) -> (f32, f32, f32, f32) {
+        match self {
+            Placeholder::Enemy => (0.3, 0.0, 0.3, 1.0),
+            Placeholder::Boss => (0.1, 0.0, 0.2, 1.0),
+            Placeholder::CrumblingPlatform => (0.5, 0.35, 0.2, 1.0),
+            Placeholder::FallingBlock => (0.4, 0.4, 0.45, 1.0),
+            Placeholder::Saw => (0.8, 0.8, 0.85, 1.0),
+            Placeholder::Projectile => (1.0, 0.4, 0.1, 1.0),
+        }
+    }
+}
+
+/// Shares the textures of `Placeholder` sprites between all entities of the same kind and size.
+#[derive(Debug, Default)]
+pub struct Placeholders {
+    textures: HashMap<(Placeholder, (u32, u32)), Texture>,
+}
+
+impl Placeholders {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn sprite(
+        &mut self,
+        ctx: &mut Context,
+        placeholder: Placeholder,
+        (w, h): (f32, f32),
+    ) -> Result<Sprite, crow::Error> {
+        let size = (w.round() as u32, h.round() as u32);
+        let texture = match self.textures.get(&(placeholder, size)) {
+            Some(texture) => texture.clone(),
+            None => {
+                let mut texture = Texture::new(ctx, size)?;
+                ctx.clear_color(&mut texture, placeholder.color());
+                self.textures.insert((placeholder, size), texture.clone());
+                texture
+            }
+        };
+
+        Ok(Sprite {
+            texture,
+            offset: (0, 0),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CameraLock {
+    /// the lower left corner of the area
+    pub position: Position,
+    pub size: (f32, f32),
+}
 
 #[derive(Default, Debug, Clone)]
 pub struct Fadeout {
@@ -1,8 +1,139 @@
+use std::{collections::BTreeMap, path::Path};
+
 use serde::{Deserialize, Serialize};
 
-use crate::data::Position;
+use crate::{
+    config::{Config, GoddessConfig, LoadError},
+    data::Position,
+};
+
+/// The save written while playing, this file is not part of the repository.
+pub const SAVE_PATH: &str = "ressources/save/save.ron";
+/// The save used if there is no save at `SAVE_PATH` yet.
+pub const DEFAULT_SAVE_PATH: &str = "ressources/save/test_save.ron";
+
+/// The names of flags set by the game itself.
+pub mod flags {
+    use crate::data::{BossId, ShrineId};
+
+    /// The goddess power absorbed by the player, increased for each destroyed shrine.
+    pub const GODDESS_POWER: &str = "goddess_power";
+
+    /// Set once the boss `id` has been defeated.
+    pub fn defeated_boss(BossId { chunk, index }: BossId) -> String {
+        format!("boss/{}/{}/{}/defeated", chunk.0, chunk.1, index)
+    }
+
+    /// Set once the shrine `id` has been destroyed.
+    pub fn destroyed_shrine(ShrineId { chunk, index }: ShrineId) -> String {
+        format!("shrine/{}/{}/{}/destroyed", chunk.0, chunk.1, index)
+    }
+
+    /// Set once the chunk at `(x, y)` has been loaded.
+    pub fn visited_chunk((x, y): (i32, i32)) -> String {
+        format!("chunk/{}/{}/visited", x, y)
+    }
+}
+
+/// Persistent values stored in the save, like opened doors or collected items.
+///
+/// Flags which were never set are treated as `0`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Flags(BTreeMap<String, i32>);
+
+impl Flags {
+    pub fn get(&self, flag: &str) -> i32 {
+        self.0.get(flag).copied().unwrap_or(0)
+    }
+
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.get(flag) != 0
+    }
+
+    pub fn set<S: Into<String>>(&mut self, flag: S, value: i32) {
+        let flag = flag.into();
+        if value == 0 {
+            self.0.remove(&flag);
+        } else {
+            self.0.insert(flag, value);
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveData {
     pub position: Position,
+    #[serde(default)]
+    pub flags: Flags,
+}
+
+impl SaveData {
+    /// Loads the save at `SAVE_PATH`, falling back to `DEFAULT_SAVE_PATH` if it does not exist.
+    pub fn load_current() -> Result<Self, LoadError> {
+        if Path::new(SAVE_PATH).exists() {
+            Self::load(SAVE_PATH)
+        } else {
+            Self::load(DEFAULT_SAVE_PATH)
+        }
+    }
+
+    /// Writes the save to `SAVE_PATH`, only logging failures.
+    pub fn persist(&self) {
+        if let Err(err) = self.store(SAVE_PATH) {
+            error!("Failed to store save: {:?}", err);
+        }
+    }
+
+    /// The goddess power absorbed by the player, one for each destroyed shrine.
+    pub fn goddess_power(&self) -> usize {
+        self.flags.get(flags::GODDESS_POWER).max(0) as usize
+    }
+
+    /// The factor by which the goddess power increases the attack damage of the player.
+    pub fn attack_factor(&self, config: &GoddessConfig) -> f32 {
+        1.0 + self.goddess_power() as f32 * config.damage_bonus
+    }
+
+    /// Returns `true` if the player absorbed enough goddess power
+    /// to survive the last transformation of the sorcerer.
+    pub fn survives_transformation(&self, config: &GoddessConfig) -> bool {
+        self.goddess_power() >= config.required_power
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_flags_are_zero() {
+        let flags = Flags::default();
+        assert_eq!(flags.get("door"), 0);
+        assert!(!flags.is_set("door"));
+    }
+
+    #[test]
+    fn set_flags() {
+        let mut flags = Flags::default();
+        flags.set("door", 1);
+        flags.set("coins", -3);
+        assert_eq!(flags.get("door"), 1);
+        assert!(flags.is_set("door"));
+        assert_eq!(flags.get("coins"), -3);
+        assert!(flags.is_set("coins"));
+
+        flags.set("door", 2);
+        assert_eq!(flags.get("door"), 2);
+    }
+
+    #[test]
+    fn setting_zero_removes_flags() {
+        let mut flags = Flags::default();
+        flags.set("door", 1);
+        flags.set("door", 0);
+        assert_eq!(flags.get("door"), 0);
+        assert!(!flags.is_set("door"));
+        assert!(flags.0.is_empty());
+    }
 }
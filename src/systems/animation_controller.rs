@@ -0,0 +1,68 @@
+use crow_ecs::{Entities, Joinable, SparseStorage, Storage};
+
+use crow_anim::{AnimationState, AnimationStorage};
+
+use crate::data::{
+    AnimationCondition, AnimationController, Attack, Attacker, Grounded, PlayerState, Velocity,
+    WallCollision,
+};
+
+/// Starts the first matching transition of each `AnimationController`.
+#[derive(Debug)]
+pub struct AnimationControllerSystem;
+
+impl AnimationControllerSystem {
+    pub fn run(
+        &mut self,
+        controllers: &SparseStorage<AnimationController>,
+        animations: &mut Storage<AnimationState>,
+        velocities: &Storage<Velocity>,
+        grounded: &Storage<Grounded>,
+        wall_collisions: &Storage<WallCollision>,
+        player_state: &SparseStorage<PlayerState>,
+        attacks: &SparseStorage<Attack>,
+        attackers: &SparseStorage<Attacker>,
+        animation_storage: &AnimationStorage,
+    ) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        for (controller, animation, entity) in (controllers, animations, Entities).join() {
+            let velocity = velocities
+                .get(entity)
+                .copied()
+                .unwrap_or(Velocity { x: 0.0, y: 0.0 });
+            let holds = |condition: &AnimationCondition| match *condition {
+                AnimationCondition::Grounded(expected) => {
+                    grounded.get(entity).is_some() == expected
+                }
+                AnimationCondition::WallContact(expected) => {
+                    wall_collisions.get(entity).is_some() == expected
+                }
+                AnimationCondition::SpeedXAbove(speed) => velocity.x.abs() >= speed,
+                AnimationCondition::SpeedXBelow(speed) => velocity.x.abs() < speed,
+                AnimationCondition::VelocityYAbove(speed) => velocity.y > speed,
+                AnimationCondition::VelocityYBelow(speed) => velocity.y <= speed,
+                AnimationCondition::PlayerState(state) => player_state.get(entity) == Some(&state),
+                AnimationCondition::AttackHit(hit) => attacks
+                    .get(entity)
+                    .map_or(false, |attack| attack.hit == hit),
+                AnimationCondition::EnemyAttackPhase(phase) => attackers
+                    .get(entity)
+                    .and_then(|attacker| attacker.current.as_ref())
+                    .map_or(false, |attack| attack.phase == phase),
+            };
+
+            // the first matching transition wins, even if it keeps the current animation
+            let current = animation.current;
+            if let Some(transition) = controller.transitions.iter().find(|transition| {
+                (transition.from.is_empty() || transition.from.contains(&current))
+                    && transition.conditions.iter().all(holds)
+            }) {
+                if transition.to != current {
+                    *animation = animation_storage.start(transition.to);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crow_ecs::{Entities, Entity, Joinable};
+
+use crate::{
+    config::BossConfig,
+    data::{
+        Attacker, Boss, BossState, Collider, ColliderType, Components, EnemyAttack, Flash, Health,
+        Mirrored, Position,
+    },
+    environment::World,
+    ressources::{CameraLock, Ressources},
+    save::flags,
+    systems::cancel_attack,
+};
+
+/// The thickness of the walls locking the player inside of an arena.
+const ARENA_WALL_THICKNESS: f32 = 20.0;
+/// The frames between toggling the flash of a transforming boss.
+const TRANSFORMATION_FLASH_INTERVAL: usize = 10;
+
+/// Controls boss fights, from entering the arena to the final transformation.
+#[derive(Debug)]
+pub struct BossSystem;
+
+impl BossSystem {
+    pub fn run(&mut self, c: &mut Components, r: &mut Ressources) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        let player = match (&c.player_state, &c.positions, &c.colliders, Entities)
+            .join()
+            .unique()
+        {
+            Ok((_state, &position, &collider, entity)) => (position, collider, entity),
+            Err(err) => {
+                error!("No unique player: {:?}", err);
+                return;
+            }
+        };
+
+        let bosses: Vec<_> = (&c.bosses, Entities)
+            .join()
+            .map(|(_, entity)| entity)
+            .collect();
+
+        // the arena is unlocked in case the boss has been unloaded
+        if !bosses.iter().any(|&boss| {
+            c.bosses
+                .get(boss)
+                .map_or(false, |boss| boss.state != BossState::Waiting)
+        }) {
+            r.camera_lock = None;
+        }
+
+        for entity in bosses {
+            let mut boss = c.bosses.get(entity).cloned().unwrap();
+            let config = match r.config.bosses.get(&boss.name) {
+                Some(config) => config,
+                None => {
+                    error!("Unknown boss: {}", boss.name);
+                    c.bosses.remove(entity);
+                    continue;
+                }
+            };
+
+            match boss.state {
+                BossState::Waiting => {
+                    let (position, collider, _) = player;
+                    let center = Position {
+                        x: position.x + collider.w / 2.0,
+                        y: position.y + collider.h / 2.0,
+                    };
+                    if in_arena(&boss, center) {
+                        start_fight(
+                            entity,
+                            &mut boss,
+                            config,
+                            c,
+                            &mut r.world,
+                            &mut r.camera_lock,
+                        );
+                    }
+                }
+                BossState::Fighting => {
+                    let health = c.healths.get(entity).map_or(0.0, |health| health.0);
+                    let next_phase = config.phases.get(boss.phase + 1);
+                    if health <= 0.0 {
+                        info!("{} starts its final transformation", boss.name);
+                        cancel_attack(entity, c, &mut r.world);
+                        c.attackers.remove(entity);
+                        boss.state = BossState::Transforming { frame: 0 };
+                    } else if next_phase
+                        .map_or(false, |phase| health <= phase.health * config.health)
+                    {
+                        cancel_attack(entity, c, &mut r.world);
+                        boss.phase += 1;
+                        boss.next_attack = 0;
+                        boss.state = BossState::PhaseTransition {
+                            frames: config.phase_transition,
+                        };
+                    } else {
+                        // `GameConfig::validate` ensures that each boss has at least one phase
+                        let pattern = &config.phases[boss.phase].pattern;
+                        let ready = c.attackers.get(entity).map_or(true, |attacker| {
+                            attacker.current.is_none() && attacker.cooldown == 0
+                        });
+                        if ready && !pattern.is_empty() {
+                            let (position, _, _) = player;
+                            face(entity, position, c);
+
+                            let attack = pattern[boss.next_attack % pattern.len()].clone();
+                            boss.next_attack += 1;
+                            c.attackers.insert(
+                                entity,
+                                Attacker {
+                                    attack,
+                                    cooldown: 0,
+                                    current: Some(EnemyAttack::new()),
+                                },
+                            );
+                        }
+                    }
+                }
+                BossState::PhaseTransition { frames } => {
+                    boss.state = match frames.saturating_sub(1) {
+                        0 => BossState::Fighting,
+                        frames => BossState::PhaseTransition { frames },
+                    };
+                }
+                BossState::Transforming { frame } => {
+                    if (frame / TRANSFORMATION_FLASH_INTERVAL) % 2 == 0 {
+                        c.flashes.insert(entity, Flash(config.transformation_flash));
+                    } else {
+                        c.flashes.remove(entity);
+                    }
+
+                    if frame + 1 < config.transformation {
+                        boss.state = BossState::Transforming { frame: frame + 1 };
+                    } else if r.last_save.survives_transformation(&r.config.goddess) {
+                        info!("{} has been defeated", boss.name);
+                        r.last_save.flags.set(flags::defeated_boss(boss.id), 1);
+                        r.last_save.persist();
+                        end_fight(entity, &mut boss, c, &mut r.world);
+                        remove_from_chunks(&mut r.world, entity);
+                        c.delete_entity(entity);
+                        r.camera_lock = None;
+                        continue;
+                    } else {
+                        // the transformation consumes the soul of the player
+                        c.flashes.remove(entity);
+                        let consume = c.new_entity();
+                        c.positions.insert(consume, boss.arena);
+                        c.colliders.insert(
+                            consume,
+                            Collider {
+                                w: boss.arena_size.0,
+                                h: boss.arena_size.1,
+                                ty: ColliderType::PlayerDamage,
+                            },
+                        );
+                        add_to_chunk(&mut r.world, entity, consume);
+                        boss.spawned.push(consume);
+                        boss.state = BossState::Transformed;
+                    }
+                }
+                BossState::Transformed => (),
+            }
+
+            c.bosses.insert(entity, boss);
+        }
+    }
+}
+
+/// Moves all bosses back to the start of their fight, used once the player respawns.
+pub fn reset_bosses(c: &mut Components, r: &mut Ressources) {
+    let bosses: Vec<_> = (&c.bosses, Entities)
+        .join()
+        .map(|(_, entity)| entity)
+        .collect();
+
+    for entity in bosses {
+        let mut boss = c.bosses.get(entity).cloned().unwrap();
+        end_fight(entity, &mut boss, c, &mut r.world);
+        boss.state = BossState::Waiting;
+        boss.phase = 0;
+        boss.next_attack = 0;
+        c.mirrored.remove(entity);
+        c.bosses.insert(entity, boss);
+    }
+
+    r.camera_lock = None;
+}
+
+fn in_arena(boss: &Boss, point: Position) -> bool {
+    boss.arena.x <= point.x
+        && point.x <= boss.arena.x + boss.arena_size.0
+        && boss.arena.y <= point.y
+        && point.y <= boss.arena.y + boss.arena_size.1
+}
+
+fn start_fight(
+    entity: Entity,
+    boss: &mut Boss,
+    config: &BossConfig,
+    c: &mut Components,
+    world: &mut World,
+    camera_lock: &mut Option<CameraLock>,
+) {
+    info!("Started the fight against {}", boss.name);
+    // give the player some time before the first attack
+    boss.state = BossState::PhaseTransition {
+        frames: config.phase_transition,
+    };
+    boss.phase = 0;
+    boss.next_attack = 0;
+
+    c.healths.insert(entity, Health(config.health));
+
+    for &x in [
+        boss.arena.x - ARENA_WALL_THICKNESS,
+        boss.arena.x + boss.arena_size.0,
+    ]
+    .iter()
+    {
+        let wall = c.new_entity();
+        c.positions.insert(wall, Position { x, y: boss.arena.y });
+        c.colliders.insert(
+            wall,
+            Collider {
+                w: ARENA_WALL_THICKNESS,
+                h: boss.arena_size.1,
+                ty: ColliderType::Environment,
+            },
+        );
+        add_to_chunk(world, entity, wall);
+        boss.spawned.push(wall);
+    }
+
+    *camera_lock = Some(CameraLock {
+        position: boss.arena,
+        size: boss.arena_size,
+    });
+}
+
+/// Removes everything spawned during the fight, including the current attack.
+fn end_fight(entity: Entity, boss: &mut Boss, c: &mut Components, world: &mut World) {
+    cancel_attack(entity, c, world);
+    c.attackers.remove(entity);
+    c.healths.remove(entity);
+    c.flashes.remove(entity);
+
+    for spawned in boss.spawned.drain(..) {
+        remove_from_chunks(world, spawned);
+        c.delete_entity(spawned);
+    }
+}
+
+fn face(entity: Entity, target: Position, c: &mut Components) {
+    if let Some(position) = c.positions.get(entity) {
+        if target.x < position.x {
+            c.mirrored.insert(entity, Mirrored);
+        } else {
+            c.mirrored.remove(entity);
+        }
+    }
+}
+
+/// Adds `entity` to the chunk of `owner`, deleting it once this chunk is unloaded.
+fn add_to_chunk(world: &mut World, owner: Entity, entity: Entity) {
+    if let Some(chunk) = world
+        .chunks
+        .iter_mut()
+        .find(|chunk| chunk.tiles.contains(&owner))
+    {
+        chunk.tiles.push(entity);
+    }
+}
+
+fn remove_from_chunks(world: &mut World, entity: Entity) {
+    for chunk in world.chunks.iter_mut() {
+        chunk.tiles.retain(|&e| e != entity);
+    }
+}
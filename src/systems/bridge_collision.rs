@@ -1,36 +1,73 @@
-use crow_ecs::{SparseStorage, Storage};
+use crow_ecs::{Entities, Joinable, SparseStorage, Storage};
 
-use crate::data::{Collider, Collision, Collisions, IgnoreBridges, Position};
+use crate::data::{
+    Collider, Collision, Collisions, DropThrough, Grounded, IgnoreBridges, Position, Velocity,
+};
+
+/// The distance an entity may already be inside of a bridge while still landing on it,
+/// prevents entities from falling through bridges after small overlaps.
+const BRIDGE_LANDING_TOLERANCE: f32 = 2.0;
 
 #[derive(Debug, Default)]
 pub struct BridgeCollisionSystem;
 impl BridgeCollisionSystem {
     pub fn run(
         &mut self,
-        positions: &Storage<Position>,
+        positions: &mut Storage<Position>,
         previous_positions: &Storage<Position>,
         colliders: &Storage<Collider>,
+        velocities: &mut Storage<Velocity>,
+        grounded: &mut Storage<Grounded>,
         ignore_bridges: &SparseStorage<IgnoreBridges>,
+        drop_throughs: &mut SparseStorage<DropThrough>,
         collisions: &mut Collisions,
     ) {
         #[cfg(feature = "profiler")]
         profile_scope!("run");
 
-        for Collision(bridge, other) in collisions.bridge.drain(..) {
-            if ignore_bridges.get(other).is_none() {
-                let bridge_pos = positions.get(bridge).copied().unwrap();
-                let bridge_pos = previous_positions
-                    .get(bridge)
-                    .copied()
-                    .unwrap_or(bridge_pos);
-                let bridge_col = colliders.get(bridge).expect("bridge collider");
+        let mut expired = Vec::new();
+        for (drop_through, entity) in (&mut *drop_throughs, Entities).join() {
+            drop_through.frames = drop_through.frames.saturating_sub(1);
+            if drop_through.frames == 0 {
+                expired.push(entity);
+            }
+        }
+
+        for entity in expired {
+            drop_throughs.remove(entity);
+        }
+
+        for Collision(bridge, other) in collisions.one_way.drain(..) {
+            let dropping = drop_throughs
+                .get(other)
+                .map_or(false, |drop_through| drop_through.bridges.contains(&bridge));
+            if ignore_bridges.get(other).is_some() || dropping {
+                continue;
+            }
+
+            let bridge_col = colliders.get(bridge).expect("bridge collider");
+            let bridge_pos = positions.get(bridge).copied().unwrap();
+            let bridge_top = bridge_col.upper_border(bridge_pos);
+            let bridge_prev_top = previous_positions
+                .get(bridge)
+                .map_or(bridge_top, |&pos| bridge_col.upper_border(pos));
+            let bridge_vel = velocities.get(bridge).copied().unwrap_or_default();
 
-                let other_pos = positions.get(other).copied().unwrap();
-                let other_pos = previous_positions.get(other).copied().unwrap_or(other_pos);
+            let other_pos = positions.get(other).copied().unwrap();
+            let other_prev_pos = previous_positions.get(other).copied().unwrap_or(other_pos);
+            let other_vel = velocities.get(other).copied().unwrap_or_default();
 
-                if bridge_col.upper_border(bridge_pos) <= other_pos.y {
-                    collisions.fixed.push(Collision(bridge, other));
+            if bridge_prev_top <= other_prev_pos.y {
+                collisions.fixed.push(Collision(bridge, other));
+            } else if bridge_prev_top - BRIDGE_LANDING_TOLERANCE <= other_prev_pos.y
+                && other_vel.y <= bridge_vel.y
+            {
+                // the entity was slightly inside of the bridge while falling onto it
+                positions.get_mut(other).unwrap().y = bridge_top;
+                if let Some(velocity) = velocities.get_mut(other) {
+                    velocity.y = bridge_vel.y;
                 }
+                grounded.insert(other, Grounded(bridge));
             }
         }
     }
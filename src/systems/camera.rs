@@ -2,7 +2,8 @@ use crow_ecs::{Joinable, SparseStorage, Storage};
 
 use crate::{
     config::CameraConfig,
-    data::{Camera, PlayerState, Position, Velocity},
+    data::{Camera, Collider, PlayerState, Position, Velocity},
+    ressources::CameraLock,
     time::Time,
 };
 
@@ -16,7 +17,9 @@ impl CameraSystem {
         positions: &Storage<Position>,
         previous_positions: &Storage<Position>,
         velocities: &mut Storage<Velocity>,
+        colliders: &Storage<Collider>,
         cameras: &SparseStorage<Camera>,
+        lock: Option<CameraLock>,
         time: &Time,
         config: &CameraConfig,
     ) {
@@ -26,11 +29,24 @@ impl CameraSystem {
         {
             Ok((_player, previous_position, position)) => {
                 let target = previous_position.copied().unwrap_or(*position);
-                for (&Camera, camera_position, velocity) in (cameras, positions, velocities).join()
+                for (&Camera, camera_position, collider, velocity) in
+                    (cameras, positions, colliders, velocities).join()
                 {
+                    let mut destination = Position {
+                        x: target.x - config.offset.0,
+                        y: target.y - config.offset.1,
+                    };
+
+                    if let Some(lock) = lock {
+                        destination.x =
+                            lock_axis(destination.x, lock.position.x, lock.size.0, collider.w);
+                        destination.y =
+                            lock_axis(destination.y, lock.position.y, lock.size.1, collider.h);
+                    }
+
                     let (diff_x, diff_y) = (
-                        target.x - camera_position.x - config.offset.0,
-                        target.y - camera_position.y - config.offset.1,
+                        destination.x - camera_position.x,
+                        destination.y - camera_position.y,
                     );
 
                     *velocity = Velocity {
@@ -43,3 +59,13 @@ impl CameraSystem {
         }
     }
 }
+
+/// Keeps a view of size `view` starting at `position` inside of the area from `start` to `start + len`,
+/// centering the view if the area is too small.
+fn lock_axis(position: f32, start: f32, len: f32, view: f32) -> f32 {
+    if len <= view {
+        start + (len - view) / 2.0
+    } else {
+        position.max(start).min(start + len - view)
+    }
+}
@@ -4,7 +4,7 @@ use crow_ecs::{Joinable, SparseStorage, Storage};
 
 use crow_anim::Sprite;
 
-use crate::data::{Camera, Collider, ColliderType, Depth, Mirrored, Position};
+use crate::data::{Camera, Collider, ColliderType, Depth, Flash, Mirrored, Position};
 
 pub fn scene<T: DrawTarget>(
     ctx: &mut Context,
@@ -13,6 +13,7 @@ pub fn scene<T: DrawTarget>(
     sprites: &Storage<Sprite>,
     depths: &Storage<Depth>,
     mirrored: &SparseStorage<Mirrored>,
+    flashes: &SparseStorage<Flash>,
     colliders: &Storage<Collider>,
     cameras: &SparseStorage<Camera>,
 ) -> Result<(), crow::Error> {
@@ -21,11 +22,12 @@ pub fn scene<T: DrawTarget>(
 
     for (&Camera, &camera_position) in (cameras, positions).join() {
         let (camera_x, camera_y) = camera_position.into();
-        for (&position, sprite, depth, mirrored, collider) in (
+        for (&position, sprite, depth, mirrored, flash, collider) in (
             positions,
             sprites,
             depths.maybe(),
             mirrored.maybe(),
+            flashes.maybe(),
             colliders.maybe(),
         )
             .join()
@@ -45,6 +47,14 @@ pub fn scene<T: DrawTarget>(
                 (x - sprite.offset.0, false)
             };
 
+            let (r, g, b) = flash.map_or((1.0, 1.0, 1.0), |flash| flash.0);
+            let color_modulation = [
+                [r, 0.0, 0.0, 0.0],
+                [0.0, g, 0.0, 0.0],
+                [0.0, 0.0, b, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ];
+
             ctx.draw(
                 target,
                 &sprite.texture,
@@ -52,6 +62,7 @@ pub fn scene<T: DrawTarget>(
                 &DrawConfig {
                     depth: depth.copied().map(From::from),
                     flip_horizontally,
+                    color_modulation,
                     ..Default::default()
                 },
             );
@@ -79,10 +90,17 @@ pub fn debug_colliders<T: DrawTarget>(
             let color = match collider.ty {
                 ColliderType::Player => (0.0, 1.0, 0.0, 0.4),
                 ColliderType::PlayerDamage => (1.0, 0.0, 0.0, 0.8),
+                ColliderType::PlayerAttack => (1.0, 0.5, 0.0, 0.8),
                 ColliderType::Environment => (0.0, 0.7, 0.7, 0.8),
                 ColliderType::Bridge => (0.0, 0.0, 1.0, 0.8),
                 ColliderType::Camera => (0.0, 0.0, 0.0, 1.0),
                 ColliderType::CameraRestriction => (0.0, 0.0, 0.0, 1.0),
+                ColliderType::Trigger => (1.0, 1.0, 0.0, 0.4),
+                ColliderType::Ladder => (0.6, 0.3, 0.0, 0.4),
+                ColliderType::GravityZone => (0.3, 0.3, 1.0, 0.3),
+                ColliderType::Shrine => (1.0, 1.0, 1.0, 0.6),
+                ColliderType::Enemy => (0.5, 0.0, 0.5, 0.8),
+                ColliderType::Projectile => (1.0, 0.3, 0.3, 0.8),
             };
 
             let xw = (x + collider.w).round() as i32;
@@ -5,7 +5,7 @@ use crow::Context;
 use crow_ecs::Joinable;
 
 use crate::{
-    data::{Components, Position},
+    data::{Components, Position, Slope},
     environment::{chunk::Chunk, Tile, CHUNK_HEIGHT, CHUNK_TILES, CHUNK_WIDTH, TILE_SIZE},
     input::{InputEvent, Key, KeyState, MouseButton},
     ressources::Ressources,
@@ -22,6 +22,37 @@ impl EditorSystem {
     }
 }
 
+/// Rebuilds the loaded chunk at `(x, y)` after changing its tile at `(_, tile_y)`.
+fn rebuild_chunk(
+    ctx: &mut Context,
+    c: &mut Components,
+    r: &mut Ressources,
+    (x, y): (i32, i32),
+    (_, tile_y): (usize, usize),
+) -> Result<(), crow::Error> {
+    let chunks = &mut r.world.chunks;
+    let above_ladders = chunks
+        .iter()
+        .find(|chunk| chunk.position == (x, y + 1))
+        .map_or(Vec::new(), |chunk| chunk.bottom_ladders.clone());
+    if let Some(chunk) = chunks.iter_mut().find(|chunk| chunk.position == (x, y)) {
+        chunk.rebuild(ctx, c, &above_ladders, &mut r.placeholders)?;
+    }
+
+    // ladders in the bottom row change the tops of the ladders below
+    if tile_y == 0 {
+        let bottom_ladders = chunks
+            .iter()
+            .find(|chunk| chunk.position == (x, y))
+            .map_or(Vec::new(), |chunk| chunk.bottom_ladders.clone());
+        if let Some(below) = chunks.iter_mut().find(|chunk| chunk.position == (x, y - 1)) {
+            below.rebuild(ctx, c, &bottom_ladders, &mut r.placeholders)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn tile_on_click(camera: Position, r: &mut Ressources) -> ((i32, i32), (usize, usize)) {
     let pos = r.input_state.cursor_position();
 
@@ -78,7 +109,12 @@ impl EditorSystem {
                 Tile::Solid => Tile::Spike,
                 Tile::Spike => Tile::Bridge,
                 Tile::Bridge => Tile::Grass,
-                Tile::Grass => Tile::Solid,
+                Tile::Grass => Tile::Ladder,
+                Tile::Ladder => Tile::Slope(Slope::all().next().unwrap()),
+                Tile::Slope(slope) => Slope::all()
+                    .skip_while(|&s| s != slope)
+                    .nth(1)
+                    .map_or(Tile::Solid, Tile::Slope),
             };
             info!("Set editor tile to {:?}", self.tile);
         }
@@ -92,24 +128,26 @@ impl EditorSystem {
         }
 
         if r.input_state.mouse(MouseButton::Left) == KeyState::Down {
-            let (chunk, tile) = tile_on_click(camera, r);
+            let (position, tile) = tile_on_click(camera, r);
 
-            let chunk_pos = r.world.chunks.iter().position(|c| c.position == chunk);
-            let chunk = if let Some(chunk) = chunk_pos.map(|pos| &mut r.world.chunks[pos]) {
+            let chunks = &mut r.world.chunks;
+            let chunk_pos = chunks.iter().position(|c| c.position == position);
+            let chunk = if let Some(chunk) = chunk_pos.map(|pos| &mut chunks[pos]) {
                 chunk
             } else {
-                r.world.chunks.push(Chunk::empty(chunk, c));
-                r.world.chunks.last_mut().unwrap()
+                chunks.push(Chunk::empty(position, c));
+                chunks.last_mut().unwrap()
             };
             chunk.data.tiles[tile.1 as usize][tile.0 as usize] = Some(self.tile);
-            chunk.rebuild(ctx, c)?;
+            rebuild_chunk(ctx, c, r, position, tile)?;
         } else if r.input_state.mouse(MouseButton::Right) == KeyState::Down {
-            let (chunk, tile) = tile_on_click(camera, r);
+            let (position, tile) = tile_on_click(camera, r);
 
-            let chunk_pos = r.world.chunks.iter().position(|c| c.position == chunk);
-            if let Some(chunk) = chunk_pos.map(|pos| &mut r.world.chunks[pos]) {
+            let chunks = &mut r.world.chunks;
+            let chunk_pos = chunks.iter().position(|c| c.position == position);
+            if let Some(chunk) = chunk_pos.map(|pos| &mut chunks[pos]) {
                 chunk.data.tiles[tile.1 as usize][tile.0 as usize] = None;
-                chunk.rebuild(ctx, c)?;
+                rebuild_chunk(ctx, c, r, position, tile)?;
             }
         }
 
@@ -0,0 +1,197 @@
+use crow_ecs::{Entities, Entity, Joinable};
+
+use crate::{
+    data::{Behaviour, BehaviourState, Components, Enemy, Mirrored, Position, Velocity},
+    physics,
+    ressources::Ressources,
+    systems::cancel_attack,
+};
+
+/// The horizontal distance at which an enemy counts as having reached its destination.
+const ARRIVAL_DISTANCE: f32 = 1.0;
+
+/// Moves enemies according to their `Behaviour` and removes killed enemies.
+#[derive(Debug)]
+pub struct EnemySystem;
+
+impl EnemySystem {
+    pub fn run(&mut self, c: &mut Components, r: &mut Ressources) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        let killed: Vec<_> = (&c.enemies, &c.healths, Entities)
+            .join()
+            .filter(|(_, health, _)| health.0 <= 0.0)
+            .map(|(enemy, _, entity)| (enemy.id, entity))
+            .collect();
+
+        for (id, entity) in killed {
+            cancel_attack(entity, c, &mut r.world);
+            for chunk in r.world.chunks.iter_mut() {
+                chunk.tiles.retain(|&e| e != entity);
+            }
+            c.delete_entity(entity);
+            r.world.killed_enemies.push(id);
+        }
+
+        let enemies: Vec<_> = (&c.enemies, &c.behaviours, &c.behaviour_states, Entities)
+            .join()
+            .map(|(&enemy, &behaviour, &state, entity)| (enemy, behaviour, state, entity))
+            .collect();
+
+        for (enemy, behaviour, state, entity) in enemies {
+            let position = match c.positions.get(entity) {
+                Some(&position) => position,
+                None => continue,
+            };
+            let target = find_target(entity, &enemy, &behaviour, c, r);
+
+            let state = match state {
+                BehaviourState::Patrol { direction } => {
+                    if let Some(target) = target {
+                        BehaviourState::Chase(target)
+                    } else {
+                        let offset = position.x - enemy.post.x;
+                        let wall = c
+                            .wall_collisions
+                            .get(entity)
+                            .map_or(false, |wall| wall.direction() == direction);
+                        if wall || offset * direction >= behaviour.patrol_distance {
+                            BehaviourState::Patrol {
+                                direction: -direction,
+                            }
+                        } else {
+                            BehaviourState::Patrol { direction }
+                        }
+                    }
+                }
+                BehaviourState::Chase(_) | BehaviourState::Return => {
+                    if let Some(target) = target {
+                        BehaviourState::Chase(target)
+                    } else if (position.x - enemy.post.x).abs() < ARRIVAL_DISTANCE {
+                        BehaviourState::default()
+                    } else {
+                        BehaviourState::Return
+                    }
+                }
+            };
+
+            let (direction, speed) = match state {
+                BehaviourState::Patrol { direction } => {
+                    if behaviour.patrol_distance > 0.0 {
+                        (direction, behaviour.speed)
+                    } else {
+                        (0.0, 0.0)
+                    }
+                }
+                BehaviourState::Chase(target) => (
+                    towards(
+                        position.x,
+                        c.positions.get(target).map_or(position.x, |pos| pos.x),
+                    ),
+                    behaviour.chase_speed,
+                ),
+                BehaviourState::Return => (towards(position.x, enemy.post.x), behaviour.speed),
+            };
+
+            let mut velocity = c
+                .velocities
+                .get(entity)
+                .copied()
+                .unwrap_or(Velocity { x: 0.0, y: 0.0 });
+            velocity.x = direction * speed;
+
+            let state = if c.grounded.get(entity).is_some()
+                && physics::at_ledge(
+                    entity,
+                    &velocity,
+                    &c.positions,
+                    &c.colliders,
+                    &c.slopes,
+                    &r.time,
+                    &r.config.collision,
+                ) {
+                velocity.x = 0.0;
+                match state {
+                    BehaviourState::Patrol { direction } => BehaviourState::Patrol {
+                        direction: -direction,
+                    },
+                    state => state,
+                }
+            } else {
+                state
+            };
+
+            if velocity.x < 0.0 {
+                c.mirrored.insert(entity, Mirrored);
+            } else if velocity.x > 0.0 {
+                c.mirrored.remove(entity);
+            }
+
+            c.velocities.insert(entity, velocity);
+            c.behaviour_states.insert(entity, state);
+        }
+    }
+}
+
+/// Returns the closest visible entity of a different faction close enough to the post of `enemy`.
+fn find_target(
+    entity: Entity,
+    enemy: &Enemy,
+    behaviour: &Behaviour,
+    c: &Components,
+    r: &Ressources,
+) -> Option<Entity> {
+    let faction = c.factions.get(entity).copied()?;
+    let collider = c.colliders.get(entity).copied()?;
+    let eye = center(*c.positions.get(entity)?, collider.w, collider.h);
+    let blocking = r.config.collision.solid_layers(collider.ty);
+
+    (&c.factions, &c.positions, &c.colliders, Entities)
+        .join()
+        .filter(|&(&other, _, _, _)| other != faction)
+        .filter_map(|(_, &position, other_col, other)| {
+            let target = center(position, other_col.w, other_col.h);
+            let offset = (target.x - eye.x, target.y - eye.y);
+            let distance = (offset.0 * offset.0 + offset.1 * offset.1).sqrt();
+            if distance > behaviour.sight_distance
+                || (target.x - enemy.post.x).abs() > behaviour.leash_distance
+            {
+                return None;
+            }
+
+            let blocked = physics::raycast(
+                &c.positions,
+                &c.colliders,
+                &c.slopes,
+                eye,
+                offset,
+                distance,
+                &blocking,
+            )
+            .is_some();
+            if blocked {
+                None
+            } else {
+                Some((distance, other))
+            }
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+        .map(|(_, other)| other)
+}
+
+/// Returns the horizontal direction from `from` to `to`, or `0.0` if they are close enough.
+fn towards(from: f32, to: f32) -> f32 {
+    if (to - from).abs() < ARRIVAL_DISTANCE {
+        0.0
+    } else {
+        (to - from).signum()
+    }
+}
+
+fn center(position: Position, w: f32, h: f32) -> Position {
+    Position {
+        x: position.x + w / 2.0,
+        y: position.y + h / 2.0,
+    }
+}
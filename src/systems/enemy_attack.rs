@@ -0,0 +1,252 @@
+use crow::Context;
+
+use crow_ecs::{Entities, Entity, Joinable};
+
+use crate::{
+    config::EnemyAttackConfig,
+    data::{
+        BehaviourState, Collider, ColliderType, Components, EnemyAttack, Flash, Mirrored, Position,
+        Velocity,
+    },
+    environment::World,
+    ressources::Ressources,
+};
+
+/// Starts and advances enemy attacks, spawning `PlayerDamage` colliders and projectiles
+/// during their active phases.
+#[derive(Debug)]
+pub struct EnemyAttackSystem;
+
+impl EnemyAttackSystem {
+    pub fn run(
+        &mut self,
+        ctx: &mut Context,
+        c: &mut Components,
+        r: &mut Ressources,
+    ) -> Result<(), crow::Error> {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        let attackers: Vec<_> = (&c.attackers, Entities)
+            .join()
+            .map(|(_, entity)| entity)
+            .collect();
+
+        for entity in attackers {
+            let mut attacker = c.attackers.get(entity).cloned().unwrap();
+            let config = match r.config.enemy_attacks.get(&attacker.attack) {
+                Some(config) => config,
+                None => {
+                    error!("Unknown enemy attack: {}", attacker.attack);
+                    c.attackers.remove(entity);
+                    continue;
+                }
+            };
+
+            if attacker.current.is_none() {
+                attacker.cooldown = attacker.cooldown.saturating_sub(1);
+                if attacker.cooldown == 0 {
+                    if let Some(direction) = target_in_range(entity, config, c) {
+                        if direction < 0.0 {
+                            c.mirrored.insert(entity, Mirrored);
+                        } else {
+                            c.mirrored.remove(entity);
+                        }
+
+                        attacker.current = Some(EnemyAttack::new());
+                    }
+                }
+            }
+
+            if let Some(mut attack) = attacker.current.take() {
+                // enemies stand still while attacking
+                if let Some(velocity) = c.velocities.get_mut(entity) {
+                    velocity.x = 0.0;
+                }
+
+                if config.is_flashing(attack.frame) {
+                    c.flashes.insert(entity, Flash(config.flash));
+                } else {
+                    c.flashes.remove(entity);
+                }
+
+                attack.phase = config.phase(attack.frame);
+                let active_phase = config.active_phase(attack.frame);
+                if active_phase != attack.active_phase {
+                    remove_hitboxes(&mut attack, entity, c, &mut r.world);
+                    if let Some(phase) = active_phase {
+                        for _ in config.active[phase].hitboxes.iter() {
+                            let hitbox = c.new_entity();
+                            attack.hitboxes.push(hitbox);
+                            // hitboxes are deleted together with the chunk of the attacker
+                            if let Some(chunk) = r
+                                .world
+                                .chunks
+                                .iter_mut()
+                                .find(|chunk| chunk.tiles.contains(&entity))
+                            {
+                                chunk.tiles.push(hitbox);
+                            }
+                        }
+
+                        if let (Some(&position), Some(&collider)) =
+                            (c.positions.get(entity), c.colliders.get(entity))
+                        {
+                            let mirrored = c.mirrored.get(entity).is_some();
+                            for spawn in config.active[phase].projectiles.iter() {
+                                let projectile = match r.config.projectiles.get(&spawn.projectile) {
+                                    Some(projectile) => projectile,
+                                    None => {
+                                        error!("Unknown projectile: {}", spawn.projectile);
+                                        continue;
+                                    }
+                                };
+
+                                let (x, velocity_x) = if mirrored {
+                                    (
+                                        collider.left_border(position)
+                                            - spawn.offset.0
+                                            - projectile.size.0,
+                                        -spawn.velocity.0,
+                                    )
+                                } else {
+                                    (
+                                        collider.right_border(position) + spawn.offset.0,
+                                        spawn.velocity.0,
+                                    )
+                                };
+
+                                r.projectiles.spawn(
+                                    ctx,
+                                    c,
+                                    &mut r.placeholders,
+                                    projectile,
+                                    Position {
+                                        x,
+                                        y: position.y + spawn.offset.1,
+                                    },
+                                    Velocity {
+                                        x: velocity_x,
+                                        y: spawn.velocity.1,
+                                    },
+                                )?;
+                            }
+                        }
+                    }
+                    attack.active_phase = active_phase;
+                }
+
+                if let (Some(phase), Some(&position), Some(&collider)) = (
+                    attack.active_phase,
+                    c.positions.get(entity),
+                    c.colliders.get(entity),
+                ) {
+                    let mirrored = c.mirrored.get(entity).is_some();
+                    for (&hitbox, config) in
+                        attack.hitboxes.iter().zip(&config.active[phase].hitboxes)
+                    {
+                        let (w, h) = config.size;
+                        let x = if mirrored {
+                            collider.left_border(position) - config.offset.0 - w
+                        } else {
+                            collider.right_border(position) + config.offset.0
+                        };
+                        let y = position.y + config.offset.1;
+
+                        c.positions.insert(hitbox, Position { x, y });
+                        c.colliders.insert(
+                            hitbox,
+                            Collider {
+                                w,
+                                h,
+                                ty: ColliderType::PlayerDamage,
+                            },
+                        );
+                    }
+                }
+
+                attack.frame += 1;
+                if attack.frame >= config.duration() {
+                    remove_hitboxes(&mut attack, entity, c, &mut r.world);
+                    c.flashes.remove(entity);
+                    attacker.cooldown = config.cooldown;
+                } else {
+                    attacker.current = Some(attack);
+                }
+            }
+
+            c.attackers.insert(entity, attacker);
+        }
+
+        Ok(())
+    }
+}
+
+/// Stops the current attack of `entity`, removing its hitboxes.
+pub fn cancel_attack(entity: Entity, c: &mut Components, world: &mut World) {
+    let attack = c
+        .attackers
+        .get_mut(entity)
+        .and_then(|attacker| attacker.current.take());
+
+    if let Some(mut attack) = attack {
+        remove_hitboxes(&mut attack, entity, c, world);
+    }
+
+    if let Some(attacker) = c.attackers.get_mut(entity) {
+        attacker.cooldown = 0;
+    }
+    c.flashes.remove(entity);
+}
+
+fn remove_hitboxes(
+    attack: &mut EnemyAttack,
+    attacker: Entity,
+    c: &mut Components,
+    world: &mut World,
+) {
+    for hitbox in attack.hitboxes.drain(..) {
+        if let Some(chunk) = world
+            .chunks
+            .iter_mut()
+            .find(|chunk| chunk.tiles.contains(&attacker))
+        {
+            chunk.tiles.retain(|&e| e != hitbox);
+        }
+        c.delete_entity(hitbox);
+    }
+}
+
+/// Returns the direction of the chased target if it is close enough to be attacked.
+fn target_in_range(entity: Entity, config: &EnemyAttackConfig, c: &Components) -> Option<f32> {
+    let target = match c.behaviour_states.get(entity) {
+        Some(&BehaviourState::Chase(target)) => target,
+        _ => return None,
+    };
+
+    if c.grounded.get(entity).is_none() {
+        return None;
+    }
+
+    let (position, collider) = (*c.positions.get(entity)?, *c.colliders.get(entity)?);
+    let (target_pos, target_col) = (*c.positions.get(target)?, *c.colliders.get(target)?);
+
+    let (direction, distance) = if target_pos.x + target_col.w / 2.0 < position.x + collider.w / 2.0
+    {
+        (
+            -1.0,
+            collider.left_border(position) - target_col.right_border(target_pos),
+        )
+    } else {
+        (
+            1.0,
+            target_col.left_border(target_pos) - collider.right_border(position),
+        )
+    };
+
+    if distance <= config.range {
+        Some(direction)
+    } else {
+        None
+    }
+}
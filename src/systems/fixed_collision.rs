@@ -7,24 +7,35 @@ use crow_ecs::{Entity, Storage};
 
 use crate::{
     data::{
-        Collider, ColliderType, Collision, CollisionDirection, Collisions, Grounded, Position,
-        Velocity, WallCollision,
+        Collider, Collision, CollisionDirection, Collisions, Grounded, Position, Velocity,
+        WallCollision,
     },
     physics::collision_direction,
 };
 
+/// The maximum height an entity standing on a slope can step up,
+/// required when walking from a slope onto an adjacent solid tile.
+const SLOPE_STEP_HEIGHT: f32 = 6.0;
+
 #[derive(Default, Debug)]
 pub struct FixedCollisionSystem {
     moved: HashMap<Entity, Vec<Entity>>,
+    crushed: Vec<Entity>,
 }
 
 impl FixedCollisionSystem {
     pub fn new() -> Self {
         FixedCollisionSystem {
             moved: HashMap::new(),
+            crushed: Vec::new(),
         }
     }
 
+    /// All entities which were squashed between solids during the last call to `run`.
+    pub fn crushed(&self) -> &[Entity] {
+        &self.crushed
+    }
+
     pub fn run(
         &mut self,
         mut positions: &mut Storage<Position>,
@@ -39,6 +50,7 @@ impl FixedCollisionSystem {
         profile_scope!("run");
 
         wall_collisions.clear();
+        self.crushed.clear();
         for &Collision(e, p) in collisions.fixed.iter() {
             self.moved.entry(p).or_insert_with(Vec::new).push(e);
         }
@@ -46,6 +58,9 @@ impl FixedCollisionSystem {
         for (other, solids) in self.moved.drain() {
             let (other_prev_pos, other_col, other_vel) =
                 prev_pos_col_vel(positions, previous_positions, colliders, velocities, other);
+            let other_pos = positions.get(other).copied().expect("other_pos");
+            // `grounded` is only set by the `SlopeCollisionSystem` at this point
+            let on_slope = grounded.get(other).is_some();
 
             let (unique, shared) =
                 solids
@@ -63,7 +78,18 @@ impl FixedCollisionSystem {
                         let dir = collision_direction(
                             (solid_prev_pos, solid_col, solid_vel),
                             (other_prev_pos, other_col, other_vel),
-                        ) as u8;
+                        );
+
+                        let solid_pos = positions.get(solid).copied().expect("solid_pos");
+                        let step_height = solid_col.upper_border(solid_pos) - other_pos.y;
+                        let dir = match dir {
+                            CollisionDirection::Left | CollisionDirection::Right
+                                if on_slope && step_height <= SLOPE_STEP_HEIGHT =>
+                            {
+                                CollisionDirection::Above
+                            }
+                            dir => dir,
+                        } as u8;
                         (unique | dir, shared & dir)
                     });
 
@@ -156,6 +182,7 @@ impl FixedCollisionSystem {
                     unique,
                     other,
                     solids,
+                    &mut self.crushed,
                     positions,
                     previous_positions,
                     grounded,
@@ -194,6 +221,7 @@ fn collision_none(
     unique: u8,
     other: Entity,
     solids: Vec<Entity>,
+    crushed: &mut Vec<Entity>,
     mut positions: &mut Storage<Position>,
     previous_positions: &Storage<Position>,
     mut grounded: &mut Storage<Grounded>,
@@ -210,12 +238,15 @@ fn collision_none(
     ) {
         (vertical, horizontal)
     } else {
-        warn!("unit is getting squashed!");
+        // solids are pushing from opposite directions
+        crushed.push(other);
+        depenetrate(other, &solids, positions, colliders);
         return;
     };
 
     if vertical == CollisionDirection::None || horizontal == CollisionDirection::None {
         warn!("unit is currently inside of a collider");
+        depenetrate(other, &solids, positions, colliders);
         return;
     }
 
@@ -303,6 +334,41 @@ fn collision_none(
     );
 }
 
+/// Moves `other` out of all `solids` using the shortest
+/// movement along a single axis.
+fn depenetrate(
+    other: Entity,
+    solids: &[Entity],
+    positions: &mut Storage<Position>,
+    colliders: &Storage<Collider>,
+) {
+    let other_pos = positions.get(other).copied().expect("other_pos");
+    let other_col = colliders.get(other).copied().expect("other_col");
+
+    let (mut up, mut down, mut left, mut right) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+    for &solid in solids {
+        let solid_pos = positions.get(solid).copied().expect("solid_pos");
+        let solid_col = colliders.get(solid).copied().expect("solid_col");
+        up = up.max(solid_col.upper_border(solid_pos) - other_col.lower_border(other_pos));
+        down = down.max(other_col.upper_border(other_pos) - solid_col.lower_border(solid_pos));
+        right = right.max(solid_col.right_border(solid_pos) - other_col.left_border(other_pos));
+        left = left.max(other_col.right_border(other_pos) - solid_col.left_border(solid_pos));
+    }
+
+    let other_pos = positions.get_mut(other).expect("other_pos");
+    if up.min(down) <= left.min(right) {
+        if up <= down {
+            other_pos.y += up;
+        } else {
+            other_pos.y -= down;
+        }
+    } else if right <= left {
+        other_pos.x += right;
+    } else {
+        other_pos.x -= left;
+    }
+}
+
 fn prev_pos_col_vel(
     positions: &Storage<Position>,
     previous_positions: &Storage<Position>,
@@ -342,15 +408,7 @@ fn resolve_collision(
             velocities.get_mut(other).unwrap_or(&mut other_vel).y = unscaled_solid_vel.y;
         }
         CollisionDirection::Above => {
-            match solid_col.ty {
-                ColliderType::Bridge | ColliderType::Environment => {
-                    grounded.insert(other, Grounded);
-                }
-                ColliderType::Player
-                | ColliderType::PlayerDamage
-                | ColliderType::Camera
-                | ColliderType::CameraRestriction => (),
-            }
+            grounded.insert(other, Grounded(solid));
 
             other_pos.y = solid_col.upper_border(solid_pos);
             // set speed equal to ground speed
@@ -361,29 +419,13 @@ fn resolve_collision(
             other_pos.x = solid_col.left_border(solid_pos) - other_col.w;
             let mut other_vel = Velocity { x: 0.0, y: 0.0 };
             velocities.get_mut(other).unwrap_or(&mut other_vel).x = unscaled_solid_vel.x;
-            match solid_col.ty {
-                ColliderType::Bridge | ColliderType::Environment => {
-                    wall_collisions.insert(other, WallCollision)
-                }
-                ColliderType::Player
-                | ColliderType::PlayerDamage
-                | ColliderType::Camera
-                | ColliderType::CameraRestriction => None,
-            };
+            wall_collisions.insert(other, WallCollision::Right);
         }
         CollisionDirection::Right => {
             other_pos.x = solid_col.right_border(solid_pos);
             let mut other_vel = Velocity { x: 0.0, y: 0.0 };
             velocities.get_mut(other).unwrap_or(&mut other_vel).x = unscaled_solid_vel.x;
-            match solid_col.ty {
-                ColliderType::Bridge | ColliderType::Environment => {
-                    wall_collisions.insert(other, WallCollision)
-                }
-                ColliderType::Player
-                | ColliderType::PlayerDamage
-                | ColliderType::Camera
-                | ColliderType::CameraRestriction => None,
-            };
+            wall_collisions.insert(other, WallCollision::Left);
         }
         CollisionDirection::None => (),
         err => panic!("resolve collision requires a simple direction: {:?}", err),
@@ -1,8 +1,10 @@
-use crow_ecs::{Joinable, Storage};
+use crow_ecs::{Joinable, SparseStorage, Storage};
 
 use crate::{
     config::GravityConfig,
-    data::{Gravity, Velocity},
+    data::{Collider, Gravity, GravityZone, IgnoreGravity, Position, Velocity},
+    environment::World,
+    physics,
     time::Time,
 };
 
@@ -13,16 +15,59 @@ impl GravitySystem {
     pub fn run(
         &mut self,
         gravity: &Storage<Gravity>,
+        gravity_zones: &SparseStorage<GravityZone>,
+        ignore_gravity: &SparseStorage<IgnoreGravity>,
+        positions: &Storage<Position>,
+        colliders: &Storage<Collider>,
         velocities: &mut Storage<Velocity>,
+        world: &World,
         time: &Time,
         gravity_config: &GravityConfig,
     ) {
         #[cfg(feature = "profiler")]
         profile_scope!("run");
 
-        for (_gravity, mut velocity) in (&gravity, velocities).join() {
-            velocity.y += gravity_config.acceleration * time.fixed_seconds();
-            velocity.y = f32::max(velocity.y, gravity_config.terminal_velocity);
+        for (gravity, velocity, &position, collider, _) in (
+            &gravity,
+            velocities,
+            positions,
+            colliders.maybe(),
+            !ignore_gravity,
+        )
+            .join()
+        {
+            let center = collider.map_or(position, |col| Position {
+                x: position.x + col.w / 2.0,
+                y: position.y + col.h / 2.0,
+            });
+            // zones may reach into neighbouring chunks, so all loaded chunks are searched
+            let zone_scale = world
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.gravity_zones.iter())
+                .find_map(|&zone| {
+                    let zone_pos = *positions.get(zone)?;
+                    let zone_col = colliders.get(zone)?;
+                    if physics::contains(zone_pos, zone_col, center) {
+                        gravity_zones.get(zone)
+                    } else {
+                        None
+                    }
+                })
+                .map_or(1.0, |zone| zone.scale);
+
+            let acceleration = gravity_config.acceleration * gravity.scale * zone_scale;
+            let terminal_velocity = gravity
+                .terminal_velocity
+                .unwrap_or(gravity_config.terminal_velocity);
+
+            velocity.y += acceleration * time.fixed_seconds();
+            velocity.y = if acceleration.is_sign_positive() {
+                // inverted gravity
+                f32::min(velocity.y, -terminal_velocity)
+            } else {
+                f32::max(velocity.y, terminal_velocity)
+            };
         }
     }
 }
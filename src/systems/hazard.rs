@@ -0,0 +1,243 @@
+use crow_ecs::{Entities, Entity, Joinable};
+
+use crate::{
+    data::{
+        Collider, ColliderType, Components, CrumbleState, FallingState, Flash, Gravity, Position,
+        Velocity,
+    },
+    physics,
+    ressources::Ressources,
+};
+
+/// The color multiplier of crumbling platforms.
+const CRUMBLE_FLASH: (f32, f32, f32) = (1.0, 0.6, 0.6);
+
+/// The frames between toggling the flash of crumbling platforms.
+const CRUMBLE_FLASH_INTERVAL: usize = 6;
+
+/// Updates crumbling platforms, falling blocks and saws.
+#[derive(Debug)]
+pub struct HazardSystem;
+
+impl HazardSystem {
+    pub fn run(&mut self, c: &mut Components, r: &mut Ressources) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        crumbling_platforms(c);
+        falling_blocks(c);
+        saws(c, r);
+    }
+}
+
+/// Restores all hazards to their initial state, used once the player respawns.
+///
+/// Positions and velocities are reset using `Resettable`.
+pub fn reset_hazards(c: &mut Components) {
+    let platforms: Vec<_> = (&c.crumbling_platforms, Entities)
+        .join()
+        .map(|(_, entity)| entity)
+        .collect();
+
+    for entity in platforms {
+        restore_platform(entity, c);
+    }
+
+    for (block, entity) in (&mut c.falling_blocks, Entities).join() {
+        block.state = FallingState::Waiting;
+        c.gravity.remove(entity);
+    }
+
+    for saw in (&mut c.saws).join() {
+        saw.target = 0;
+    }
+}
+
+fn crumbling_platforms(c: &mut Components) {
+    let standing: Vec<Entity> = (&c.grounded).join().map(|grounded| grounded.0).collect();
+
+    let platforms: Vec<_> = (&c.crumbling_platforms, Entities)
+        .join()
+        .map(|(platform, entity)| (platform.state, entity))
+        .collect();
+
+    for (state, entity) in platforms {
+        let platform = c.crumbling_platforms.get(entity).cloned().unwrap();
+        match state {
+            CrumbleState::Intact => {
+                if standing.contains(&entity) {
+                    set_state(
+                        entity,
+                        CrumbleState::Crumbling {
+                            frames: platform.delay,
+                        },
+                        c,
+                    );
+                }
+            }
+            CrumbleState::Crumbling { frames } => {
+                if (frames / CRUMBLE_FLASH_INTERVAL) % 2 == 0 {
+                    c.flashes.insert(entity, Flash(CRUMBLE_FLASH));
+                } else {
+                    c.flashes.remove(entity);
+                }
+
+                match frames.saturating_sub(1) {
+                    0 => {
+                        c.colliders.remove(entity);
+                        c.sprites.remove(entity);
+                        c.flashes.remove(entity);
+                        set_state(
+                            entity,
+                            CrumbleState::Broken {
+                                frames: platform.respawn,
+                            },
+                            c,
+                        );
+                    }
+                    frames => set_state(entity, CrumbleState::Crumbling { frames }, c),
+                }
+            }
+            CrumbleState::Broken { frames } => {
+                if frames > 0 {
+                    set_state(entity, CrumbleState::Broken { frames: frames - 1 }, c);
+                } else if let Some(&position) = c.positions.get(entity) {
+                    // wait until the platform does not trap anything
+                    let blocked = physics::overlapping(
+                        &c.positions,
+                        &c.colliders,
+                        position,
+                        platform.size,
+                        &[ColliderType::Player, ColliderType::Enemy],
+                    )
+                    .next()
+                    .is_some();
+                    if !blocked {
+                        restore_platform(entity, c);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn set_state(entity: Entity, state: CrumbleState, c: &mut Components) {
+    if let Some(platform) = c.crumbling_platforms.get_mut(entity) {
+        platform.state = state;
+    }
+}
+
+fn restore_platform(entity: Entity, c: &mut Components) {
+    if let Some(platform) = c.crumbling_platforms.get_mut(entity) {
+        platform.state = CrumbleState::Intact;
+        c.colliders.insert(
+            entity,
+            Collider {
+                w: platform.size.0,
+                h: platform.size.1,
+                ty: ColliderType::Environment,
+            },
+        );
+        c.sprites.insert(entity, platform.sprite.clone());
+        c.flashes.remove(entity);
+    }
+}
+
+fn falling_blocks(c: &mut Components) {
+    let players: Vec<_> = (&c.player_state, &c.positions, &c.colliders)
+        .join()
+        .map(|(_, &position, &collider)| (position, collider))
+        .collect();
+
+    let blocks: Vec<_> = (&c.falling_blocks, Entities)
+        .join()
+        .map(|(block, entity)| (*block, entity))
+        .collect();
+
+    for (mut block, entity) in blocks {
+        let (position, collider) = match (c.positions.get(entity), c.colliders.get(entity)) {
+            (Some(&position), Some(&collider)) => (position, collider),
+            _ => continue,
+        };
+
+        match block.state {
+            FallingState::Waiting => {
+                let bottom = collider.lower_border(position);
+                let triggered = players.iter().any(|&(player_pos, player_col)| {
+                    let top = player_col.upper_border(player_pos);
+                    player_col.left_border(player_pos) < collider.right_border(position)
+                        && player_col.right_border(player_pos) > collider.left_border(position)
+                        && top <= bottom
+                        && bottom - top <= block.trigger_depth
+                });
+
+                if triggered {
+                    c.gravity.insert(entity, Gravity::default());
+                    block.state = FallingState::Falling;
+                }
+            }
+            FallingState::Falling => {
+                // falling blocks do not interact with other solids, so landing is checked here
+                let ground = physics::overlapping(
+                    &c.positions,
+                    &c.colliders,
+                    position,
+                    (collider.w, collider.h),
+                    &[ColliderType::Environment, ColliderType::Bridge],
+                )
+                .filter(|&other| other != entity)
+                .filter_map(|other| {
+                    let other_col = c.colliders.get(other)?;
+                    Some(other_col.upper_border(*c.positions.get(other)?))
+                })
+                .fold(None, |highest: Option<f32>, top| {
+                    Some(highest.map_or(top, |highest| highest.max(top)))
+                });
+
+                if let Some(top) = ground {
+                    c.positions.insert(
+                        entity,
+                        Position {
+                            x: position.x,
+                            y: top,
+                        },
+                    );
+                    c.velocities.insert(entity, Velocity { x: 0.0, y: 0.0 });
+                    c.gravity.remove(entity);
+                    block.state = FallingState::Landed;
+                }
+            }
+            FallingState::Landed => (),
+        }
+
+        c.falling_blocks.insert(entity, block);
+    }
+}
+
+fn saws(c: &mut Components, r: &Ressources) {
+    let step_time = r.time.fixed_seconds();
+    for (saw, position, velocity) in (&mut c.saws, &mut c.positions, &mut c.velocities).join() {
+        if saw.path.is_empty() {
+            continue;
+        }
+
+        let target = saw.path[saw.target];
+        let distance = ((target.x - position.x).powi(2) + (target.y - position.y).powi(2)).sqrt();
+        if distance <= saw.speed * step_time {
+            *position = target;
+            saw.target = (saw.target + 1) % saw.path.len();
+        }
+
+        let target = saw.path[saw.target];
+        let offset = (target.x - position.x, target.y - position.y);
+        let distance = (offset.0 * offset.0 + offset.1 * offset.1).sqrt();
+        *velocity = if distance > 0.0 {
+            Velocity {
+                x: offset.0 / distance * saw.speed,
+                y: offset.1 / distance * saw.speed,
+            }
+        } else {
+            Velocity { x: 0.0, y: 0.0 }
+        };
+    }
+}
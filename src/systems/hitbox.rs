@@ -0,0 +1,46 @@
+use crow_ecs::{Joinable, SparseStorage, Storage};
+
+use crate::{
+    config::CollisionConfig,
+    data::{Collider, Health, Hitbox, Position},
+    physics,
+};
+
+/// Applies the damage of each `Hitbox` to the entities it overlaps with.
+///
+/// As hitboxes are usually moved without a velocity, this does not
+/// rely on the collisions found by the `PhysicsSystem`.
+#[derive(Debug)]
+pub struct HitboxSystem;
+
+impl HitboxSystem {
+    pub fn run(
+        &mut self,
+        positions: &Storage<Position>,
+        colliders: &Storage<Collider>,
+        hitboxes: &mut SparseStorage<Hitbox>,
+        healths: &mut SparseStorage<Health>,
+        config: &CollisionConfig,
+    ) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        for (hitbox, &position, collider) in (hitboxes, positions, colliders).join() {
+            let targets = config.damage_targets(collider.ty);
+            for target in physics::overlapping(
+                positions,
+                colliders,
+                position,
+                (collider.w, collider.h),
+                &targets,
+            ) {
+                if !hitbox.hits.contains(&target) {
+                    if let Some(health) = healths.get_mut(target) {
+                        health.0 -= hitbox.damage;
+                        hitbox.hits.push(target);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     config::{InputBufferConfig, InputConfig},
-    input::InputEvent,
-    ressources::JumpBuffer,
+    input::{InputAction, InputEvent},
+    ressources::InputBuffer,
 };
 
 #[derive(Debug)]
@@ -11,20 +11,20 @@ impl InputBufferSystem {
     pub fn run(
         &mut self,
         events: &[InputEvent],
-        space: &mut Option<JumpBuffer>,
+        input_buffer: &mut InputBuffer,
         buffer_config: &InputBufferConfig,
         input_config: &InputConfig,
     ) {
         #[cfg(feature = "profiler")]
         profile_scope!("run");
 
-        if let Some(JumpBuffer(c)) = *space {
-            *space = c.checked_sub(1).map(JumpBuffer);
-        }
+        input_buffer.update();
 
         for event in events {
-            if InputEvent::KeyDown(input_config.jump) == *event {
-                *space = Some(JumpBuffer(buffer_config.jump_buffer_frames))
+            for &action in InputAction::all() {
+                if InputEvent::KeyDown(input_config.action_key(action)) == *event {
+                    input_buffer.press(action, buffer_config.frames(action));
+                }
             }
         }
     }
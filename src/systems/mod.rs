@@ -3,29 +3,49 @@ use std::mem;
 use crow::Context;
 
 mod animation;
+mod animation_controller;
+mod boss;
 mod bridge_collision;
 mod camera;
 pub mod draw;
+mod enemy;
+mod enemy_attack;
 mod fadeout;
 mod fixed_collision;
 mod gravity;
+mod hazard;
+mod hitbox;
 mod input_buffer;
 mod physics;
 mod player;
+mod projectile;
+mod shrine;
+mod slope_collision;
+mod trigger;
 
 #[cfg(feature = "editor")]
 mod editor;
 
 pub use crate::environment::EnvironmentSystem;
 pub use animation::AnimationSystem;
+pub use animation_controller::AnimationControllerSystem;
+pub use boss::{reset_bosses, BossSystem};
 pub use bridge_collision::BridgeCollisionSystem;
 pub use camera::CameraSystem;
+pub use enemy::EnemySystem;
+pub use enemy_attack::{cancel_attack, EnemyAttackSystem};
 pub use fadeout::FadeoutSystem;
 pub use fixed_collision::FixedCollisionSystem;
 pub use gravity::GravitySystem;
+pub use hazard::{reset_hazards, HazardSystem};
+pub use hitbox::HitboxSystem;
 pub use input_buffer::InputBufferSystem;
 pub use physics::PhysicsSystem;
 pub use player::PlayerStateMachine;
+pub use projectile::ProjectileSystem;
+pub use shrine::ShrineSystem;
+pub use slope_collision::SlopeCollisionSystem;
+pub use trigger::TriggerSystem;
 
 #[cfg(feature = "editor")]
 pub use editor::EditorSystem;
@@ -42,10 +62,20 @@ pub struct Systems {
     pub gravity: GravitySystem,
     pub physics: PhysicsSystem,
     pub bridge_collision: BridgeCollisionSystem,
+    pub slope_collision: SlopeCollisionSystem,
+    pub trigger: TriggerSystem,
+    pub shrine: ShrineSystem,
     pub fadeout: FadeoutSystem,
     pub fixed_collision: FixedCollisionSystem,
     pub player: PlayerStateMachine,
+    pub projectile: ProjectileSystem,
+    pub hazard: HazardSystem,
+    pub hitbox: HitboxSystem,
+    pub enemy: EnemySystem,
+    pub boss: BossSystem,
+    pub enemy_attack: EnemyAttackSystem,
     pub environment: EnvironmentSystem,
+    pub animation_controller: AnimationControllerSystem,
     pub animation: AnimationSystem,
     #[cfg(feature = "editor")]
     pub editor: EditorSystem,
@@ -65,10 +95,20 @@ impl Systems {
             gravity: GravitySystem,
             physics: PhysicsSystem::new(),
             bridge_collision: BridgeCollisionSystem,
+            slope_collision: SlopeCollisionSystem,
+            trigger: TriggerSystem::new(),
+            shrine: ShrineSystem,
             fadeout: FadeoutSystem,
             fixed_collision: FixedCollisionSystem::new(),
             player: PlayerStateMachine,
+            projectile: ProjectileSystem,
+            hazard: HazardSystem,
+            hitbox: HitboxSystem,
+            enemy: EnemySystem,
+            boss: BossSystem,
+            enemy_attack: EnemyAttackSystem,
             environment: EnvironmentSystem,
+            animation_controller: AnimationControllerSystem,
             animation: AnimationSystem,
             #[cfg(feature = "editor")]
             editor: EditorSystem::new(),
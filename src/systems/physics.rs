@@ -1,7 +1,11 @@
-use crow_ecs::{Entities, Entity, Joinable, Storage};
+use crow_ecs::{Entities, Entity, Joinable, SparseStorage, Storage};
 
 use crate::{
-    data::{Collider, ColliderType, Collision, Collisions, Grounded, Position, Velocity},
+    config::CollisionConfig,
+    data::{
+        Collider, ColliderType, Collision, Collisions, Grounded, Interaction, Position, Slope,
+        Velocity,
+    },
     physics,
     time::Time,
 };
@@ -20,10 +24,12 @@ impl PhysicsSystem {
         &mut self,
         velocities: &Storage<Velocity>,
         colliders: &Storage<Collider>,
+        slopes: &SparseStorage<Slope>,
         previous_positions: &mut Storage<Position>,
         mut positions: &mut Storage<Position>,
         grounded: &mut Storage<Grounded>,
         time: &Time,
+        config: &CollisionConfig,
     ) -> &mut Collisions {
         #[cfg(feature = "profiler")]
         profile_scope!("run");
@@ -46,7 +52,12 @@ impl PhysicsSystem {
         while let Some((&a_pos, &a_collider, a_entity, _moved)) = iter.next() {
             for (&b_pos, &b_collider, b_entity, _moved) in iter.clone() {
                 if physics::is_collision(a_pos, a_collider, b_pos, b_collider) {
-                    self.resolve_collisions((a_entity, a_collider.ty), (b_entity, b_collider.ty))
+                    self.resolve_collisions(
+                        (a_entity, a_collider.ty),
+                        (b_entity, b_collider.ty),
+                        slopes,
+                        config,
+                    )
                 }
             }
         }
@@ -59,7 +70,12 @@ impl PhysicsSystem {
                 (&positions, &colliders, Entities, !&previous_positions).join()
             {
                 if physics::is_collision(a_pos, a_collider, b_pos, b_collider) {
-                    self.resolve_collisions((a_entity, a_collider.ty), (b_entity, b_collider.ty))
+                    self.resolve_collisions(
+                        (a_entity, a_collider.ty),
+                        (b_entity, b_collider.ty),
+                        slopes,
+                        config,
+                    )
                 }
             }
         }
@@ -67,36 +83,39 @@ impl PhysicsSystem {
         &mut self.collisions
     }
 
-    fn resolve_collisions(&mut self, a: (Entity, ColliderType), b: (Entity, ColliderType)) {
-        match (a, b) {
-            ((e, ColliderType::Environment), (p, ColliderType::Player))
-            | ((p, ColliderType::Player), (e, ColliderType::Environment))
-            | ((e, ColliderType::CameraRestriction), (p, ColliderType::Camera))
-            | ((p, ColliderType::Camera), (e, ColliderType::CameraRestriction)) => {
-                self.collisions.fixed.push(Collision(e, p))
-            }
-            ((b, ColliderType::Bridge), (p, ColliderType::Player))
-            | ((p, ColliderType::Player), (b, ColliderType::Bridge)) => {
-                self.collisions.bridge.push(Collision(b, p))
-            }
-            ((p, ColliderType::Player), (d, ColliderType::PlayerDamage))
-            | ((d, ColliderType::PlayerDamage), (p, ColliderType::Player)) => {
-                self.collisions.player_damage.push(Collision(p, d))
+    fn resolve_collisions(
+        &mut self,
+        a: (Entity, ColliderType),
+        b: (Entity, ColliderType),
+        slopes: &SparseStorage<Slope>,
+        config: &CollisionConfig,
+    ) {
+        self.add_collision(a, b, slopes, config);
+        self.add_collision(b, a, slopes, config);
+    }
+
+    fn add_collision(
+        &mut self,
+        (source, source_ty): (Entity, ColliderType),
+        (target, target_ty): (Entity, ColliderType),
+        slopes: &SparseStorage<Slope>,
+        config: &CollisionConfig,
+    ) {
+        if let Some(interaction) = config.interaction(source_ty, target_ty) {
+            let collision = Collision(source, target);
+            match interaction {
+                Interaction::Solid => {
+                    if slopes.get(source).is_some() {
+                        self.collisions.slope.push(collision)
+                    } else {
+                        self.collisions.fixed.push(collision)
+                    }
+                }
+                Interaction::OneWay => self.collisions.one_way.push(collision),
+                Interaction::Damage => self.collisions.damage.push(collision),
+                Interaction::Sensor => self.collisions.sensor.push(collision),
+                Interaction::Impact => self.collisions.impact.push(collision),
             }
-            ((_, ColliderType::Environment), (_, ColliderType::Environment))
-            | ((_, ColliderType::Environment), (_, ColliderType::Bridge))
-            | ((_, ColliderType::Bridge), (_, ColliderType::Environment))
-            | ((_, ColliderType::Environment), (_, ColliderType::PlayerDamage))
-            | ((_, ColliderType::PlayerDamage), (_, ColliderType::Environment))
-            | ((_, ColliderType::Player), (_, ColliderType::Player))
-            | ((_, ColliderType::Bridge), (_, ColliderType::Bridge))
-            | ((_, ColliderType::Bridge), (_, ColliderType::PlayerDamage))
-            | ((_, ColliderType::PlayerDamage), (_, ColliderType::Bridge))
-            | ((_, ColliderType::PlayerDamage), (_, ColliderType::PlayerDamage))
-            | ((_, ColliderType::Camera), _)
-            | (_, (_, ColliderType::Camera))
-            | ((_, ColliderType::CameraRestriction), _)
-            | (_, (_, ColliderType::CameraRestriction)) => {}
         }
     }
 }
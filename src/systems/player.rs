@@ -1,32 +1,46 @@
-use crow_ecs::{Entities, Entity, Joinable, SparseStorage};
-
-use crow_anim::{AnimationState, AnimationStorage};
+use crow_ecs::{Entities, Entity, Joinable, SparseStorage, Storage};
 
 use crate::{
+    config::{AttackConfig, CollisionConfig, LedgeConfig},
     data::{
-        Collision, Collisions, Components, Grounded, IgnoreBridges, Mirrored, PlayerAnimations,
-        PlayerState, Velocity, WallCollision,
+        Attack, Collider, ColliderType, Collision, Collisions, Components, CoyoteTime, DropThrough,
+        Grounded, Hitbox, IgnoreBridges, IgnoreGravity, InputLock, Jumping, LedgeGrab, Mirrored,
+        PlayerState, Position, Roll, RollCooldown, Slope, Stat, Surface, Velocity,
     },
     init,
-    input::KeyState,
-    ressources::{DelayedAction, Fadeout, JumpBuffer, Ressources},
+    input::{InputAction, KeyState},
+    physics,
+    ressources::{DelayedAction, Fadeout, InputBuffer, Ressources},
+    time::Time,
 };
 
-// FIXME: use a config file instead
-const RUNNING_THRESHHOLD: f32 = 15.0;
+/// The distance below the feet of the player in which ladders can be grabbed
+/// while climbing down.
+const LADDER_PROBE_OFFSET: f32 = 1.0;
+
+/// The distance below the feet of the player in which bridges are dropped through.
+const BRIDGE_PROBE_OFFSET: f32 = 1.0;
+
+/// The vertical distance between the hands of the player and a ledge
+/// at which the ledge can still be grabbed, in addition to the distance fallen during the last frame.
+const LEDGE_GRAB_TOLERANCE: f32 = 1.0;
 
 #[derive(Debug)]
 pub struct PlayerStateMachine;
 
 impl PlayerStateMachine {
-    pub fn run(&mut self, c: &mut Components, r: &mut Ressources, collisions: &Collisions) {
+    pub fn run(
+        &mut self,
+        c: &mut Components,
+        r: &mut Ressources,
+        collisions: &Collisions,
+        crushed: &[Entity],
+    ) {
         #[cfg(feature = "profiler")]
         profile_scope!("run");
 
-        for (state, animation, player_animations, velocity, grounded, wall_collision, entity) in (
+        for (state, velocity, grounded, wall_collision, entity) in (
             &mut c.player_state,
-            &mut c.animations,
-            &c.player_animations,
             &mut c.velocities,
             (&c.grounded).maybe(),
             (&c.wall_collisions).maybe(),
@@ -34,9 +48,68 @@ impl PlayerStateMachine {
         )
             .join()
         {
+            let cooldown_over =
+                c.roll_cooldowns
+                    .get_mut(entity)
+                    .map_or(false, |RollCooldown(frames)| {
+                        *frames = frames.saturating_sub(1);
+                        *frames == 0
+                    });
+            if cooldown_over {
+                c.roll_cooldowns.remove(entity);
+            }
+
+            let coyote_over = c
+                .coyote_time
+                .get_mut(entity)
+                .map_or(false, |CoyoteTime(frames)| {
+                    *frames = frames.saturating_sub(1);
+                    *frames == 0
+                });
+            if coyote_over {
+                c.coyote_time.remove(entity);
+            }
+
+            let lock_over = c
+                .input_locks
+                .get_mut(entity)
+                .map_or(false, |InputLock(frames)| {
+                    *frames = frames.saturating_sub(1);
+                    *frames == 0
+                });
+            if lock_over {
+                c.input_locks.remove(entity);
+            }
+            let input_locked = c.input_locks.get(entity).is_some();
+            let pressing_into_wall = !input_locked
+                && wall_collision.map_or(false, |wall| {
+                    r.input_state
+                        .axis(r.config.input.left, r.config.input.right)
+                        * wall.direction()
+                        > 0.5
+                });
+            let climb_direction = r.input_state.axis(r.config.input.down, r.config.input.up);
+
             if let Some(new_state) = match *state {
-                PlayerState::Grounded | PlayerState::Airborne => {
-                    on_player_damage(entity, &collisions.player_damage)
+                PlayerState::Grounded
+                | PlayerState::Airborne
+                | PlayerState::Attacking
+                | PlayerState::WallSliding
+                | PlayerState::Climbing
+                | PlayerState::LedgeHang
+                | PlayerState::LedgeClimb => on_player_damage(entity, &collisions.damage)
+                    .or_else(|| on_player_crushed(entity, crushed)),
+                PlayerState::Rolling => {
+                    let invincible = c
+                        .rolls
+                        .get(entity)
+                        .map_or(false, |roll| r.config.player.roll.is_invincible(roll.frame));
+                    if invincible {
+                        None
+                    } else {
+                        on_player_damage(entity, &collisions.damage)
+                    }
+                    .or_else(|| on_player_crushed(entity, crushed))
                 }
                 PlayerState::Dying | PlayerState::Dead => None,
             } {
@@ -44,9 +117,6 @@ impl PlayerStateMachine {
                     new_state,
                     entity,
                     velocity,
-                    animation,
-                    player_animations,
-                    &r.animation_storage,
                     &mut c.ignore_bridges,
                     &mut r.fadeout,
                     &mut r.delayed_actions,
@@ -54,46 +124,245 @@ impl PlayerStateMachine {
                 *state = new_state;
             }
 
+            let mut roll_finished = false;
+            let mut attack_finished = false;
+            let mut ledge_climbed = false;
             match *state {
-                PlayerState::Grounded | PlayerState::Airborne => {
-                    let direction = r
-                        .input_state
-                        .axis(r.config.input.left, r.config.input.right);
+                PlayerState::Grounded
+                | PlayerState::Airborne
+                | PlayerState::Attacking
+                | PlayerState::WallSliding => {
+                    // the player stops while attacking
+                    let direction = if *state == PlayerState::Attacking {
+                        0.0
+                    } else {
+                        r.input_state
+                            .axis(r.config.input.left, r.config.input.right)
+                    };
 
-                    if direction < -0.5 {
+                    if input_locked {
+                        // keep the current direction after a wall jump
+                    } else if direction < -0.5 {
                         c.mirrored.insert(entity, Mirrored);
                     } else if direction > 0.5 {
                         c.mirrored.remove(entity);
                     }
 
-                    let acceleration = if state == &mut PlayerState::Grounded {
-                        r.config.player.grounded_acceleration
-                    } else {
-                        r.config.player.airborne_acceleration
+                    let surface = match (*state, grounded) {
+                        (PlayerState::Grounded, Some(&Grounded(ground)))
+                        | (PlayerState::Attacking, Some(&Grounded(ground))) => {
+                            c.surfaces.get(ground).copied().unwrap_or_default()
+                        }
+                        _ => Surface::default(),
+                    };
+
+                    let acceleration = match *state {
+                        PlayerState::Airborne | PlayerState::WallSliding => {
+                            r.config.player.airborne_acceleration
+                        }
+                        _ => r.config.player.grounded_acceleration * surface.acceleration,
                     } * r.time.fixed_seconds();
 
-                    let target_speed = r.config.player.movement_speed * direction;
+                    let target_speed =
+                        r.config.player.movement_speed * direction + surface.conveyor_speed;
                     let speed_difference = target_speed - velocity.x;
 
-                    velocity.x += if speed_difference.abs() > acceleration {
-                        acceleration.copysign(speed_difference)
-                    } else {
-                        speed_difference
-                    };
+                    if !input_locked {
+                        velocity.x += if speed_difference.abs() > acceleration {
+                            acceleration.copysign(speed_difference)
+                        } else {
+                            speed_difference
+                        };
+                    }
+
+                    if *state == PlayerState::WallSliding {
+                        velocity.y = velocity.y.max(-r.config.player.wall.slide_speed);
+                    }
+
+                    if *state == PlayerState::Attacking {
+                        if let Some(attack) = c.attacks.get_mut(entity) {
+                            attack.frame += 1;
+                            let combo = &r.config.player.combo;
+                            let hit = &combo[attack.hit];
+                            if hit.is_recovering(attack.frame)
+                                && attack.hit + 1 < combo.len()
+                                && r.input_buffer.consume(InputAction::Attack)
+                            {
+                                attack.hit += 1;
+                                attack.frame = 0;
+                            } else {
+                                attack_finished = attack.frame >= hit.duration();
+                            }
+                        }
+                    }
+                }
+                PlayerState::Rolling => {
+                    if let Some(roll) = c.rolls.get_mut(entity) {
+                        roll.frame += 1;
+                        velocity.x = r.config.player.roll.speed(&r.time) * roll.direction;
+                        roll_finished = roll.frame >= r.config.player.roll.duration
+                            || wall_collision.is_some()
+                            || physics::at_ledge(
+                                entity,
+                                velocity,
+                                &c.positions,
+                                &c.colliders,
+                                &c.slopes,
+                                &r.time,
+                                &r.config.collision,
+                            );
+
+                        if roll_finished {
+                            velocity.x = 0.0;
+                        }
+                    }
+                }
+                PlayerState::Climbing => {
+                    velocity.x = 0.0;
+                    velocity.y = climb_direction * r.config.player.climb_speed;
+                }
+                PlayerState::LedgeHang => {
+                    *velocity = Velocity { x: 0.0, y: 0.0 };
+                }
+                PlayerState::LedgeClimb => {
+                    *velocity = Velocity { x: 0.0, y: 0.0 };
+                    if let Some(grab) = c.ledge_grabs.get_mut(entity) {
+                        grab.frame += 1;
+                        if grab.frame >= r.config.player.ledge.climb_frames {
+                            // stand on top of the ledge
+                            let collider = c.colliders.get(entity).copied().unwrap();
+                            let position = c.positions.get_mut(entity).unwrap();
+                            position.x = if grab.direction > 0.0 {
+                                grab.corner.x
+                            } else {
+                                grab.corner.x - collider.w
+                            };
+                            position.y = grab.corner.y;
+                            ledge_climbed = true;
+                        }
+                    }
                 }
                 PlayerState::Dying | PlayerState::Dead => (),
             }
 
+            // ladders below the player can only be grabbed while climbing down
+            let wants_to_climb = if climb_direction > 0.5 {
+                find_ladder(entity, &c.positions, &c.colliders, 0.0).is_some()
+            } else if climb_direction < -0.5 {
+                find_ladder(entity, &c.positions, &c.colliders, LADDER_PROBE_OFFSET).is_some()
+            } else {
+                false
+            };
+
+            let ledge = if *state == PlayerState::Airborne
+                && velocity.y <= 0.0
+                && climb_direction >= -0.5
+            {
+                let direction = if c.mirrored.get(entity).is_some() {
+                    -1.0
+                } else {
+                    1.0
+                };
+                find_ledge(
+                    entity,
+                    direction,
+                    velocity,
+                    &c.positions,
+                    &c.colliders,
+                    &c.slopes,
+                    &r.time,
+                    &r.config.player.ledge,
+                    &r.config.collision,
+                )
+                .map(|corner| LedgeGrab {
+                    corner,
+                    direction,
+                    frame: 0,
+                })
+            } else {
+                None
+            };
+            let toward_ledge = c.ledge_grabs.get(entity).map_or(0.0, |grab| {
+                r.input_state
+                    .axis(r.config.input.left, r.config.input.right)
+                    * grab.direction
+            });
+
             if let Some(new_state) = match *state {
                 PlayerState::Grounded => {
-                    if grounded == None {
+                    if grounded.is_none() {
                         Some(PlayerState::Airborne)
+                    } else if wants_to_climb {
+                        Some(PlayerState::Climbing)
                     } else {
                         None
                     }
                 }
                 PlayerState::Airborne => {
-                    if grounded == Some(&Grounded) {
+                    if grounded.is_some() {
+                        Some(PlayerState::Grounded)
+                    } else if wants_to_climb && velocity.y <= 0.0 {
+                        Some(PlayerState::Climbing)
+                    } else if ledge.is_some() {
+                        Some(PlayerState::LedgeHang)
+                    } else if pressing_into_wall && velocity.y <= 0.0 {
+                        Some(PlayerState::WallSliding)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::WallSliding => {
+                    if grounded.is_some() {
+                        Some(PlayerState::Grounded)
+                    } else if wants_to_climb {
+                        Some(PlayerState::Climbing)
+                    } else if !pressing_into_wall {
+                        Some(PlayerState::Airborne)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::Climbing => {
+                    if find_ladder(entity, &c.positions, &c.colliders, 0.0).is_none() {
+                        // prevent the player from jumping when leaving the top of a ladder
+                        velocity.y = velocity.y.min(0.0);
+                        Some(PlayerState::Airborne)
+                    } else if grounded.is_some() && climb_direction <= 0.0 {
+                        Some(PlayerState::Grounded)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::LedgeHang => {
+                    if climb_direction > 0.5 || toward_ledge > 0.5 {
+                        Some(PlayerState::LedgeClimb)
+                    } else if climb_direction < -0.5 || toward_ledge < -0.5 {
+                        // let go of the ledge
+                        Some(PlayerState::Airborne)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::LedgeClimb => {
+                    if ledge_climbed {
+                        Some(PlayerState::Grounded)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::Rolling => {
+                    if grounded.is_none() {
+                        Some(PlayerState::Airborne)
+                    } else if roll_finished {
+                        Some(PlayerState::Grounded)
+                    } else {
+                        None
+                    }
+                }
+                PlayerState::Attacking => {
+                    if grounded.is_none() {
+                        Some(PlayerState::Airborne)
+                    } else if attack_finished {
                         Some(PlayerState::Grounded)
                     } else {
                         None
@@ -101,13 +370,40 @@ impl PlayerStateMachine {
                 }
                 PlayerState::Dying | PlayerState::Dead => None,
             } {
+                let left_ground = match *state {
+                    PlayerState::Grounded | PlayerState::Rolling | PlayerState::Attacking => true,
+                    _ => false,
+                };
+                if new_state == PlayerState::Airborne
+                    && left_ground
+                    && r.config.player.coyote_frames > 0
+                {
+                    // the player walked of a ledge instead of jumping
+                    c.coyote_time
+                        .insert(entity, CoyoteTime(r.config.player.coyote_frames));
+                }
+
+                if let (PlayerState::LedgeHang, Some(grab)) = (new_state, ledge) {
+                    hang_on_ledge(
+                        entity,
+                        grab,
+                        &mut c.positions,
+                        &c.colliders,
+                        &r.config.player.ledge,
+                    );
+                    c.ledge_grabs.insert(entity, grab);
+                    *velocity = Velocity { x: 0.0, y: 0.0 };
+                }
+
+                if new_state == PlayerState::Climbing {
+                    attach_to_ladder(entity, &mut c.positions, &c.colliders);
+                    *velocity = Velocity { x: 0.0, y: 0.0 };
+                }
+
                 initialize_state(
                     new_state,
                     entity,
                     velocity,
-                    animation,
-                    player_animations,
-                    &r.animation_storage,
                     &mut c.ignore_bridges,
                     &mut r.fadeout,
                     &mut r.delayed_actions,
@@ -115,56 +411,234 @@ impl PlayerStateMachine {
                 *state = new_state;
             }
 
+            if *state == PlayerState::WallSliding && maybe_jump(&mut r.input_buffer) {
+                // jump away from the wall
+                let direction = -wall_collision.map_or(0.0, |wall| wall.direction());
+                let (speed_x, speed_y) = r.config.player.wall.jump_speed;
+                velocity.x = speed_x * direction;
+                velocity.y = speed_y;
+                if direction < 0.0 {
+                    c.mirrored.insert(entity, Mirrored);
+                } else {
+                    c.mirrored.remove(entity);
+                }
+
+                c.input_locks
+                    .insert(entity, InputLock(r.config.player.wall.jump_input_lock));
+                c.jumping.insert(entity, Jumping);
+                *state = PlayerState::Airborne;
+            }
+
             if match *state {
-                PlayerState::Grounded => maybe_jump(&mut r.pressed_space),
-                PlayerState::Airborne | PlayerState::Dying | PlayerState::Dead => false,
+                PlayerState::Grounded | PlayerState::Climbing | PlayerState::LedgeHang => {
+                    maybe_jump(&mut r.input_buffer)
+                }
+                PlayerState::Airborne => {
+                    c.coyote_time.get(entity).is_some() && maybe_jump(&mut r.input_buffer)
+                }
+                PlayerState::Rolling
+                | PlayerState::Attacking
+                | PlayerState::WallSliding
+                | PlayerState::LedgeClimb
+                | PlayerState::Dying
+                | PlayerState::Dead => false,
             } {
                 velocity.y = r.config.player.jump_speed;
-                *animation = r.animation_storage.start(player_animations.jumping);
 
+                c.coyote_time.remove(entity);
+                c.jumping.insert(entity, Jumping);
                 *state = PlayerState::Airborne;
             }
 
-            match state {
-                PlayerState::Grounded | PlayerState::Airborne => {
-                    if r.input_state.key(r.config.input.down) == KeyState::Down {
-                        c.ignore_bridges.insert(entity, IgnoreBridges);
-                    } else {
-                        c.ignore_bridges.remove(entity);
-                    }
+            if *state != PlayerState::Airborne {
+                c.coyote_time.remove(entity);
+                c.jumping.remove(entity);
+            } else if c.jumping.get(entity).is_some() {
+                if velocity.y <= 0.0 {
+                    c.jumping.remove(entity);
+                } else if r.input_state.key(r.config.input.jump) == KeyState::Up {
+                    // releasing the jump key early results in a lower jump
+                    velocity.y = velocity.y.min(r.config.player.jump_cut_speed);
+                    c.jumping.remove(entity);
                 }
-                PlayerState::Dying | PlayerState::Dead => (),
             }
 
-            if *state == PlayerState::Airborne
-                && velocity.y.is_sign_negative()
-                && animation.current == player_animations.jumping
+            if *state == PlayerState::Grounded
+                && c.roll_cooldowns.get(entity).is_none()
+                && r.input_buffer.consume(InputAction::Roll)
             {
-                *animation = r.animation_storage.start(player_animations.start_falling);
+                let direction = if c.mirrored.get(entity).is_some() {
+                    -1.0
+                } else {
+                    1.0
+                };
+                c.rolls.insert(
+                    entity,
+                    Roll {
+                        frame: 0,
+                        direction,
+                    },
+                );
+                initialize_state(
+                    PlayerState::Rolling,
+                    entity,
+                    velocity,
+                    &mut c.ignore_bridges,
+                    &mut r.fadeout,
+                    &mut r.delayed_actions,
+                );
+                *state = PlayerState::Rolling;
+            }
+
+            if *state == PlayerState::Grounded
+                && !r.config.player.combo.is_empty()
+                && r.input_buffer.consume(InputAction::Attack)
+            {
+                // keep the hitbox of the previous attack so it can be removed
+                let hitbox = c.attacks.get(entity).and_then(|attack| attack.hitbox);
+                c.attacks.insert(
+                    entity,
+                    Attack {
+                        hit: 0,
+                        frame: 0,
+                        hitbox,
+                    },
+                );
+                initialize_state(
+                    PlayerState::Attacking,
+                    entity,
+                    velocity,
+                    &mut c.ignore_bridges,
+                    &mut r.fadeout,
+                    &mut r.delayed_actions,
+                );
+                *state = PlayerState::Attacking;
             }
 
-            if animation.current == player_animations.run_into_obstacle && wall_collision == None {
-                *animation = r.animation_storage.start(player_animations.idle);
+            match *state {
+                PlayerState::Climbing | PlayerState::LedgeHang | PlayerState::LedgeClimb => {
+                    c.ignore_gravity.insert(entity, IgnoreGravity);
+                }
+                _ => {
+                    c.ignore_gravity.remove(entity);
+                }
             }
 
-            if *state == PlayerState::Grounded {
-                if velocity.x.abs() >= RUNNING_THRESHHOLD {
-                    if animation.current == player_animations.idle {
-                        *animation = r.animation_storage.start(player_animations.running);
-                    }
-                } else if animation.current == player_animations.running {
-                    if animation.current == player_animations.running
-                        && wall_collision == Some(&WallCollision)
-                    {
-                        *animation = r
-                            .animation_storage
-                            .start(player_animations.run_into_obstacle);
-                    } else {
-                        *animation = r.animation_storage.start(player_animations.idle);
-                    }
+            match *state {
+                PlayerState::LedgeHang | PlayerState::LedgeClimb => (),
+                _ => {
+                    c.ledge_grabs.remove(entity);
+                }
+            }
+
+            if *state != PlayerState::Rolling && c.rolls.get(entity).is_some() {
+                c.rolls.remove(entity);
+                c.roll_cooldowns
+                    .insert(entity, RollCooldown(r.config.player.roll.cooldown));
+            }
+
+            if *state == PlayerState::Grounded && r.input_buffer.is_buffered(InputAction::Drop) {
+                let bridges =
+                    bridges_below(entity, &c.positions, &c.colliders, &r.config.collision);
+                if !bridges.is_empty() {
+                    r.input_buffer.consume(InputAction::Drop);
+                    c.drop_throughs.insert(
+                        entity,
+                        DropThrough {
+                            bridges,
+                            frames: r.config.player.drop_through_frames,
+                        },
+                    );
+                }
+            }
+
+            match state {
+                PlayerState::Grounded | PlayerState::Airborne => {
+                    c.ignore_bridges.remove(entity);
+                }
+                PlayerState::Climbing => {
+                    // allow climbing through the top of ladders
+                    c.ignore_bridges.insert(entity, IgnoreBridges);
                 }
+                PlayerState::Rolling
+                | PlayerState::Attacking
+                | PlayerState::WallSliding
+                | PlayerState::LedgeHang
+                | PlayerState::LedgeClimb
+                | PlayerState::Dying
+                | PlayerState::Dead => (),
             }
         }
+
+        update_attack_hitboxes(c, &r.config.player.combo);
+    }
+}
+
+/// Spawns the hitbox of an attack during its active frames and removes it afterwards.
+fn update_attack_hitboxes(c: &mut Components, combo: &[AttackConfig]) {
+    let attackers: Vec<_> = (&c.player_state, &c.attacks, Entities)
+        .join()
+        .map(|(&state, &attack, entity)| (entity, state, attack))
+        .collect();
+
+    for (entity, state, mut attack) in attackers {
+        let active = state == PlayerState::Attacking
+            && combo
+                .get(attack.hit)
+                .map_or(false, |hit| hit.is_active(attack.frame));
+
+        if active {
+            let hit = &combo[attack.hit];
+            let (position, collider) = match (c.positions.get(entity), c.colliders.get(entity)) {
+                (Some(&position), Some(&collider)) => (position, collider),
+                _ => continue,
+            };
+
+            let (w, h) = hit.hitbox_size;
+            let x = if c.mirrored.get(entity).is_some() {
+                collider.left_border(position) - hit.hitbox_offset.0 - w
+            } else {
+                collider.right_border(position) + hit.hitbox_offset.0
+            };
+            let y = position.y + hit.hitbox_offset.1;
+
+            let hitbox = match attack.hitbox {
+                Some(hitbox) => hitbox,
+                None => {
+                    let hitbox = c.new_entity();
+                    let damage = c
+                        .stat_modifiers
+                        .get(entity)
+                        .map_or(hit.damage, |m| m.apply(Stat::AttackDamage, hit.damage));
+                    c.hitboxes.insert(
+                        hitbox,
+                        Hitbox {
+                            damage,
+                            hits: Vec::new(),
+                        },
+                    );
+                    hitbox
+                }
+            };
+            c.positions.insert(hitbox, Position { x, y });
+            c.colliders.insert(
+                hitbox,
+                Collider {
+                    w,
+                    h,
+                    ty: ColliderType::PlayerAttack,
+                },
+            );
+            attack.hitbox = Some(hitbox);
+        } else if let Some(hitbox) = attack.hitbox.take() {
+            c.delete_entity(hitbox);
+        }
+
+        if state != PlayerState::Attacking && attack.hitbox.is_none() {
+            c.attacks.remove(entity);
+        } else {
+            c.attacks.insert(entity, attack);
+        }
     }
 }
 
@@ -172,21 +646,12 @@ fn initialize_state(
     state: PlayerState,
     player: Entity,
     velocity: &mut Velocity,
-    animation: &mut AnimationState,
-    player_animations: &PlayerAnimations,
-    animation_storage: &AnimationStorage,
     ignore_bridges: &mut SparseStorage<IgnoreBridges>,
     fadeout: &mut Option<Fadeout>,
     delayed_actions: &mut Vec<DelayedAction>,
 ) {
+    // animations are selected by the `AnimationControllerSystem`
     match state {
-        PlayerState::Grounded => {
-            *animation = animation_storage.start(player_animations.idle);
-        }
-        PlayerState::Airborne => {
-            *animation = animation_storage.start(player_animations.start_falling);
-            // jumping is handled directly after `maybe_jump`
-        }
         PlayerState::Dying => {
             // prevent the player from sliding of falling through bridges
             // while still falling to the ground
@@ -205,10 +670,9 @@ fn initialize_state(
             delayed_actions.push(DelayedAction {
                 frames_left,
                 action: Box::new(|ctx, s, c, r| {
-                    *c = Components::new();
-                    r.reset();
-                    init::player(ctx, c, r)?;
-                    init::camera(c, r);
+                    init::respawn(c, r);
+                    s.trigger.reset();
+                    // load the chunks around the last save
                     s.environment.run(ctx, c, r)
                 }),
             });
@@ -217,8 +681,8 @@ fn initialize_state(
     }
 }
 
-fn on_player_damage(entity: Entity, player_damage: &[Collision]) -> Option<PlayerState> {
-    for &Collision(player, _damage) in player_damage.iter() {
+fn on_player_damage(entity: Entity, damage: &[Collision]) -> Option<PlayerState> {
+    for &Collision(_damage, player) in damage.iter() {
         if player == entity {
             return Some(PlayerState::Dying);
         }
@@ -227,7 +691,179 @@ fn on_player_damage(entity: Entity, player_damage: &[Collision]) -> Option<Playe
     None
 }
 
-fn maybe_jump(pressed_space: &mut Option<JumpBuffer>) -> bool {
-    // use take to prevent double jmp after bonk
-    pressed_space.take().is_some()
+fn on_player_crushed(entity: Entity, crushed: &[Entity]) -> Option<PlayerState> {
+    if crushed.contains(&entity) {
+        Some(PlayerState::Dying)
+    } else {
+        None
+    }
+}
+
+/// Returns a ladder overlapping `entity` or a ladder at most `below` beneath it.
+fn find_ladder(
+    entity: Entity,
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    below: f32,
+) -> Option<Entity> {
+    let (position, collider) = match (positions.get(entity), colliders.get(entity)) {
+        (Some(&position), Some(&collider)) => (position, collider),
+        _ => return None,
+    };
+
+    let area = Position {
+        x: position.x,
+        y: position.y - below,
+    };
+    physics::overlapping(
+        positions,
+        colliders,
+        area,
+        (collider.w, collider.h + below),
+        &[ColliderType::Ladder],
+    )
+    .next()
+}
+
+/// Horizontally centers `entity` on the ladder it is climbing.
+fn attach_to_ladder(
+    entity: Entity,
+    positions: &mut Storage<Position>,
+    colliders: &Storage<Collider>,
+) {
+    if let Some(ladder) = find_ladder(entity, positions, colliders, LADDER_PROBE_OFFSET) {
+        let ladder_pos = positions.get(ladder).copied().unwrap();
+        let ladder_col = colliders.get(ladder).copied().unwrap();
+        let collider = colliders.get(entity).copied().unwrap();
+        positions.get_mut(entity).unwrap().x = ladder_pos.x + (ladder_col.w - collider.w) / 2.0;
+    }
+}
+
+/// Returns the upper corner of a ledge in front of the hands of `entity`.
+///
+/// A ledge is the top of a `Solid` collider without any other solid colliders above it.
+fn find_ledge(
+    entity: Entity,
+    direction: f32,
+    velocity: &Velocity,
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    slopes: &SparseStorage<Slope>,
+    time: &Time,
+    ledge_config: &LedgeConfig,
+    collision_config: &CollisionConfig,
+) -> Option<Position> {
+    let (position, collider) = match (positions.get(entity), colliders.get(entity)) {
+        (Some(&position), Some(&collider)) => (position, collider),
+        _ => return None,
+    };
+
+    let layers = collision_config.solid_layers(collider.ty);
+    let hands = collider.upper_border(position) - ledge_config.hang_offset;
+    // the hands of the player may have moved past the ledge during the last frame
+    let tolerance = velocity.y.abs() * time.fixed_seconds() + LEDGE_GRAB_TOLERANCE;
+    let area = Position {
+        x: if direction > 0.0 {
+            collider.right_border(position)
+        } else {
+            collider.left_border(position) - ledge_config.grab_distance
+        },
+        y: hands - tolerance,
+    };
+
+    let corner = physics::overlapping(
+        positions,
+        colliders,
+        area,
+        (ledge_config.grab_distance, tolerance * 2.0),
+        &layers,
+    )
+    .filter(|&ledge| slopes.get(ledge).is_none())
+    .map(|ledge| {
+        let ledge_pos = positions.get(ledge).copied().unwrap();
+        let ledge_col = colliders.get(ledge).copied().unwrap();
+        Position {
+            x: if direction > 0.0 {
+                ledge_col.left_border(ledge_pos)
+            } else {
+                ledge_col.right_border(ledge_pos)
+            },
+            y: ledge_col.upper_border(ledge_pos),
+        }
+    })
+    .filter(|corner| (corner.y - hands).abs() <= tolerance)
+    .max_by(|a, b| a.y.partial_cmp(&b.y).unwrap())?;
+
+    // the player must be able to stand on top of the ledge
+    let above = Position {
+        x: if direction > 0.0 {
+            corner.x
+        } else {
+            corner.x - collider.w
+        },
+        y: corner.y,
+    };
+    if physics::overlapping(
+        positions,
+        colliders,
+        above,
+        (collider.w, collider.h),
+        &layers,
+    )
+    .next()
+    .is_none()
+    {
+        Some(corner)
+    } else {
+        None
+    }
+}
+
+/// Snaps `entity` to the hang position of `grab`.
+fn hang_on_ledge(
+    entity: Entity,
+    grab: LedgeGrab,
+    positions: &mut Storage<Position>,
+    colliders: &Storage<Collider>,
+    config: &LedgeConfig,
+) {
+    let collider = colliders.get(entity).copied().unwrap();
+    let position = positions.get_mut(entity).unwrap();
+    position.x = if grab.direction > 0.0 {
+        grab.corner.x - collider.w
+    } else {
+        grab.corner.x
+    };
+    position.y = grab.corner.y + config.hang_offset - collider.h;
+}
+
+/// Returns all bridges `entity` is standing on.
+fn bridges_below(
+    entity: Entity,
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    config: &CollisionConfig,
+) -> Vec<Entity> {
+    let (position, collider) = match (positions.get(entity), colliders.get(entity)) {
+        (Some(&position), Some(&collider)) => (position, collider),
+        _ => return Vec::new(),
+    };
+
+    let feet = Position {
+        x: position.x,
+        y: position.y - BRIDGE_PROBE_OFFSET,
+    };
+    physics::overlapping(
+        positions,
+        colliders,
+        feet,
+        (collider.w, BRIDGE_PROBE_OFFSET),
+        &config.one_way_layers(collider.ty),
+    )
+    .collect()
+}
+
+fn maybe_jump(input_buffer: &mut InputBuffer) -> bool {
+    // consume the jump to prevent double jmp after bonk
+    input_buffer.consume(InputAction::Jump)
 }
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use crow_ecs::{Entities, Entity, Joinable};
+
+use crate::{
+    data::{
+        Collider, Collision, CollisionDirection, Collisions, Components, IgnoreGravity, Position,
+        ProjectileHit, Velocity,
+    },
+    physics,
+    ressources::Ressources,
+};
+
+/// Handles projectiles hitting the environment and removes expired projectiles.
+#[derive(Debug)]
+pub struct ProjectileSystem;
+
+impl ProjectileSystem {
+    pub fn run(&mut self, c: &mut Components, r: &mut Ressources, collisions: &Collisions) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        let mut hits: HashMap<Entity, Vec<Entity>> = HashMap::new();
+        for &Collision(solid, projectile) in collisions.impact.iter() {
+            if c.projectiles.get(projectile).is_some() {
+                hits.entry(projectile).or_default().push(solid);
+            }
+        }
+
+        for (projectile, solids) in hits {
+            let on_hit = c.projectiles.get(projectile).unwrap().on_hit;
+            match on_hit {
+                ProjectileHit::Destroy => r.projectiles.release(c, projectile),
+                ProjectileHit::Bounce => {
+                    let (prev_pos, col, vel) = prev_pos_col_vel(c, projectile);
+                    let direction = solids.iter().fold(0b0000, |direction, &solid| {
+                        let solid = prev_pos_col_vel(c, solid);
+                        direction | physics::collision_direction(solid, (prev_pos, col, vel)) as u8
+                    });
+
+                    let (mut flip_x, mut flip_y) = (
+                        direction & CollisionDirection::Left as u8 != 0
+                            || direction & CollisionDirection::Right as u8 != 0,
+                        direction & CollisionDirection::Above as u8 != 0
+                            || direction & CollisionDirection::Below as u8 != 0,
+                    );
+                    if !flip_x && !flip_y {
+                        // the projectile already started inside of the collider
+                        flip_x = true;
+                        flip_y = true;
+                    }
+
+                    c.positions.insert(projectile, prev_pos);
+                    if let Some(velocity) = c.velocities.get_mut(projectile) {
+                        if flip_x {
+                            velocity.x = -velocity.x;
+                        }
+                        if flip_y {
+                            velocity.y = -velocity.y;
+                        }
+                    }
+                }
+                ProjectileHit::Stick => {
+                    let (prev_pos, _, _) = prev_pos_col_vel(c, projectile);
+                    c.positions.insert(projectile, prev_pos);
+                    c.velocities.remove(projectile);
+                    c.ignore_gravity.insert(projectile, IgnoreGravity);
+                }
+            }
+        }
+
+        let expired: Vec<_> = (&mut c.projectiles, Entities)
+            .join()
+            .filter_map(|(projectile, entity)| {
+                projectile.lifetime = projectile.lifetime.saturating_sub(1);
+                if projectile.lifetime == 0 {
+                    Some(entity)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for entity in expired {
+            r.projectiles.release(c, entity);
+        }
+    }
+}
+
+fn prev_pos_col_vel(c: &Components, entity: Entity) -> (Position, Collider, Velocity) {
+    let position = c.positions.get(entity).copied().unwrap();
+    (
+        c.previous_positions
+            .get(entity)
+            .copied()
+            .unwrap_or(position),
+        c.colliders.get(entity).copied().unwrap(),
+        c.velocities.get(entity).copied().unwrap_or_default(),
+    )
+}
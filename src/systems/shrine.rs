@@ -0,0 +1,62 @@
+use crow_ecs::{Entities, Joinable};
+
+use crate::{
+    data::{Collision, Components, ModifierSource, Stat, TriggerEvents},
+    ressources::Ressources,
+    save::flags,
+};
+
+/// Updates the save once the player walks past a shrine and
+/// grants goddess power once a shrine is destroyed.
+#[derive(Debug)]
+pub struct ShrineSystem;
+
+impl ShrineSystem {
+    pub fn run(&mut self, events: &TriggerEvents, c: &mut Components, r: &mut Ressources) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        for &Collision(sensor, other) in events.enter.iter() {
+            if let (Some(shrine), Some(_)) = (c.shrines.get(sensor), c.player_state.get(other)) {
+                let position = (shrine.spawn.x, shrine.spawn.y);
+                if (r.last_save.position.x, r.last_save.position.y) != position {
+                    info!("Activated shrine at {:?}", position);
+                    r.last_save.position = shrine.spawn;
+                    r.last_save.persist();
+                }
+            }
+        }
+
+        let destroyed: Vec<_> = (&c.shrines, &c.healths, Entities)
+            .join()
+            .filter(|(_, health, _)| health.0 <= 0.0)
+            .map(|(shrine, _, entity)| (shrine.id, entity))
+            .collect();
+
+        if destroyed.is_empty() {
+            return;
+        }
+
+        for (id, entity) in destroyed {
+            info!("Destroyed shrine {:?}", id);
+            for chunk in r.world.chunks.iter_mut() {
+                chunk.tiles.retain(|&e| e != entity);
+            }
+            c.delete_entity(entity);
+
+            let flag = flags::destroyed_shrine(id);
+            if !r.last_save.flags.is_set(&flag) {
+                r.last_save.flags.set(flag, 1);
+                let power = r.last_save.flags.get(flags::GODDESS_POWER);
+                r.last_save.flags.set(flags::GODDESS_POWER, power + 1);
+            }
+        }
+
+        let factor = r.last_save.attack_factor(&r.config.goddess);
+        for (modifiers, _) in (&mut c.stat_modifiers, &c.player_state).join() {
+            modifiers.set(ModifierSource::GoddessPower, Stat::AttackDamage, factor);
+        }
+
+        r.last_save.persist();
+    }
+}
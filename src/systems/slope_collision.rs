@@ -0,0 +1,78 @@
+use crow_ecs::{SparseStorage, Storage};
+
+use crate::{
+    data::{Collider, Collision, Collisions, Grounded, Position, Slope, Velocity},
+    time::Time,
+};
+
+/// The distance an entity may hover above a slope while still being pulled onto it,
+/// prevents entities from bouncing while running down a slope.
+const SLOPE_SNAP_DISTANCE: f32 = 1.0;
+
+#[derive(Debug, Default)]
+pub struct SlopeCollisionSystem;
+
+impl SlopeCollisionSystem {
+    pub fn run(
+        &mut self,
+        positions: &mut Storage<Position>,
+        grounded: &mut Storage<Grounded>,
+        velocities: &mut Storage<Velocity>,
+        colliders: &Storage<Collider>,
+        slopes: &SparseStorage<Slope>,
+        collisions: &mut Collisions,
+        time: &Time,
+    ) {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        for Collision(slope_entity, other) in collisions.slope.drain(..) {
+            let slope_pos = positions.get(slope_entity).copied().unwrap();
+            let slope_col = colliders.get(slope_entity).copied().unwrap();
+            let slope = slopes.get(slope_entity).copied().expect("slope");
+
+            let other_col = colliders.get(other).copied().unwrap();
+            let mut default_vel = Velocity { x: 0.0, y: 0.0 };
+            let other_vel = velocities.get_mut(other).unwrap_or(&mut default_vel);
+            let other_pos = positions.get_mut(other).unwrap();
+
+            // entities stand on the slope using their horizontal center
+            let x = other_pos.x + other_col.w / 2.0 - slope_pos.x;
+            if slope.is_past_lower_end(x, &slope_col) {
+                // only the empty corner of the slope is touched
+                continue;
+            }
+
+            let inside = x >= 0.0 && x <= slope_col.w;
+            let height = slope.height(x, &slope_col);
+            let tolerance = (other_vel.x.abs() * slope.steepness() + other_vel.y.abs())
+                * time.fixed_seconds()
+                + SLOPE_SNAP_DISTANCE;
+
+            if slope.ceiling {
+                let surface = slope_col.upper_border(slope_pos) - height;
+                let depth = other_col.upper_border(*other_pos) - surface;
+                if depth > tolerance {
+                    // hitting the vertical side of the slope
+                    collisions.fixed.push(Collision(slope_entity, other));
+                } else if depth > 0.0 {
+                    other_pos.y = surface - other_col.h;
+                    other_vel.y = other_vel.y.min(0.0);
+                }
+            } else {
+                let surface = slope_col.lower_border(slope_pos) + height;
+                let depth = surface - other_pos.y;
+                if depth > tolerance {
+                    // hitting the vertical side of the slope
+                    collisions.fixed.push(Collision(slope_entity, other));
+                } else if depth > 0.0 || (inside && depth > -tolerance && other_vel.y <= 0.0) {
+                    other_pos.y = surface;
+                    if other_vel.y <= 0.0 {
+                        other_vel.y = 0.0;
+                        grounded.insert(other, Grounded(slope_entity));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use crow_ecs::{Entities, Entity, Joinable, Storage};
+
+use crate::{
+    config::CollisionConfig,
+    data::{Collider, ColliderType, Collision, Position, TriggerEvents},
+    physics,
+};
+
+/// Tracks sensor overlaps across frames to generate `TriggerEvents`.
+#[derive(Debug, Default)]
+pub struct TriggerSystem {
+    active: HashSet<(Entity, Entity)>,
+    events: TriggerEvents,
+}
+
+impl TriggerSystem {
+    pub fn new() -> Self {
+        TriggerSystem::default()
+    }
+
+    /// Forgets all current overlaps without emitting `exit` events,
+    /// required once all entities are deleted.
+    pub fn reset(&mut self) {
+        self.active.clear();
+        self.events.clear();
+    }
+
+    pub fn run(
+        &mut self,
+        positions: &Storage<Position>,
+        colliders: &Storage<Collider>,
+        config: &CollisionConfig,
+    ) -> &TriggerEvents {
+        #[cfg(feature = "profiler")]
+        profile_scope!("run");
+
+        self.events.clear();
+
+        // `PhysicsSystem` ignores entities at rest, so overlaps are checked here
+        let pairs = config.sensor_pairs();
+        let source_layers: Vec<_> = pairs.iter().map(|&(source, _)| source).collect();
+        let target_layers: Vec<_> = pairs.iter().map(|&(_, target)| target).collect();
+        let sensors = colliders_on(positions, colliders, &source_layers);
+        let targets = colliders_on(positions, colliders, &target_layers);
+
+        let mut current = HashSet::new();
+        for &(sensor_pos, sensor_col, sensor) in sensors.iter() {
+            for &(other_pos, other_col, other) in targets.iter() {
+                if sensor != other
+                    && pairs.contains(&(sensor_col.ty, other_col.ty))
+                    && physics::is_collision(sensor_pos, sensor_col, other_pos, other_col)
+                {
+                    current.insert((sensor, other));
+                }
+            }
+        }
+
+        for &(sensor, other) in current.iter() {
+            if self.active.contains(&(sensor, other)) {
+                self.events.stay.push(Collision(sensor, other));
+            } else {
+                self.events.enter.push(Collision(sensor, other));
+            }
+        }
+
+        for &(sensor, other) in self.active.difference(&current) {
+            self.events.exit.push(Collision(sensor, other));
+        }
+
+        self.active = current;
+        &self.events
+    }
+}
+
+/// Collects all colliders on one of the given `layers`.
+fn colliders_on(
+    positions: &Storage<Position>,
+    colliders: &Storage<Collider>,
+    layers: &[ColliderType],
+) -> Vec<(Position, Collider, Entity)> {
+    (positions, colliders, Entities)
+        .join()
+        .filter(|(_, collider, _)| layers.contains(&collider.ty))
+        .map(|(&position, &collider, entity)| (position, collider, entity))
+        .collect()
+}